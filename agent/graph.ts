@@ -10,6 +10,7 @@ import * as dotenv from 'dotenv';
 dotenv.config();
 
 import { StateGraph, END } from '@langchain/langgraph';
+import { createHash } from 'crypto';
 import { Connection, PublicKey } from '@solana/web3.js';
 import express from 'express';
 import cors from 'cors';
@@ -188,6 +189,10 @@ function addGlobalLog(entry: LogEntry, marketId?: string) {
     broadcastToMarket('global', wsData);
 }
 
+function sha256Bytes(text: string): Uint8Array {
+    return new Uint8Array(createHash('sha256').update(text).digest());
+}
+
 function cleanJsonString(text: string): string {
     let clean = text.replace(/```json/g, '').replace(/```/g, '').trim();
     const firstBracket = clean.indexOf('[');
@@ -248,6 +253,7 @@ interface AgentState {
     factConfidences: number[];
     decision: 'YES' | 'NO' | 'UNCERTAIN' | null;
     reasoning: string;
+    confidence: number; // Judge's confidence in the decision, 0-100
     iterations: number;
     logs: LogEntry[];
     evidenceUrls: string[];
@@ -471,6 +477,7 @@ async function judgeNode(state: AgentState): Promise<Partial<AgentState>> {
         return {
             decision: decision as 'YES' | 'NO' | 'UNCERTAIN',
             reasoning: judgment.reasoning,
+            confidence: Math.min(Math.max(Number(judgment.confidence) || 0, 0), 100),
             iterations: state.iterations + 1
         };
     } catch (e: any) {
@@ -483,6 +490,7 @@ async function judgeNode(state: AgentState): Promise<Partial<AgentState>> {
         return {
             decision: 'UNCERTAIN',
             reasoning: 'Error in judgment process',
+            confidence: 0,
             iterations: state.iterations + 1
         };
     }
@@ -556,13 +564,18 @@ async function executorNode(state: AgentState): Promise<Partial<AgentState>> {
             sentiment: 'Positive'
         });
 
-        // Resolve market on-chain
+        // Resolve market on-chain, committing to the judge's confidence and the
+        // researcher and judge outputs behind the verdict
         const outcome = decision === 'YES' ? 1 : 0;
         const result = await solanaAgent.resolveMarket({
             marketPda: new PublicKey(marketPda || solanaAgent.findMarketPda(marketId)[0]),
             marketId,
             outcome,
-            ipfsTranscriptHash: ipfsHash
+            ipfsTranscriptHash: ipfsHash,
+            confidenceBps: Math.round(state.confidence * 100),
+            modelId: MODEL_NAME,
+            researcherHash: sha256Bytes(JSON.stringify({ facts, factConfidences: state.factConfidences })),
+            judgeHash: sha256Bytes(JSON.stringify({ decision, reasoning, confidence: state.confidence }))
         });
 
         if (result.success) {
@@ -689,6 +702,7 @@ const workflow = new StateGraph<AgentState>({
         factConfidences: { reducer: (a, b) => b ?? a, default: () => [] },
         decision: { reducer: (a, b) => b ?? a, default: () => null },
         reasoning: { reducer: (a, b) => b ?? a, default: () => '' },
+        confidence: { reducer: (a, b) => b ?? a, default: () => 0 },
         iterations: { reducer: (a, b) => b ?? a, default: () => 0 },
         logs: { reducer: (a, b) => a ? a.concat(b || []) : b || [], default: () => [] },
         evidenceUrls: { reducer: (a, b) => b ?? a, default: () => [] },
@@ -823,6 +837,7 @@ server.post('/resolve', async (req, res) => {
             factConfidences: [],
             decision: null,
             reasoning: '',
+            confidence: 0,
             logs: [],
             evidenceUrls: allEvidence,
             ipfsTranscriptCid: '',
@@ -1051,44 +1066,6 @@ server.post('/mint-nft', async (req, res) => {
     }
 });
 
-// Cred Faucet - Grant 100 Cred to a wallet (for testing)
-server.post('/faucet', async (req, res) => {
-    const { walletAddress } = req.body;
-
-    if (!walletAddress) {
-        return res.status(400).json({ error: 'walletAddress is required' });
-    }
-
-    console.log(`💰 Faucet request for wallet: ${walletAddress}`);
-
-    try {
-        // Call the agent's earnCred function
-        const result = await solanaAgent.earnCred(walletAddress, 100_000_000, 'faucet_grant');
-
-        addGlobalLog({
-            speaker: 'System',
-            message: `💰 Faucet: Granted 100 Cred to ${walletAddress.substring(0, 8)}...`,
-            timestamp: Date.now(),
-            sentiment: 'Positive'
-        });
-
-        res.json({
-            success: true,
-            message: '100 Cred granted! Refresh the page to see your new balance.',
-            walletAddress,
-            amount: 100,
-            signature: result.signature,
-        });
-    } catch (err: any) {
-        console.error('Faucet error:', err);
-        res.json({
-            success: false,
-            message: `Faucet currently unavailable: ${err.message}. Try initializing a new vault instead.`,
-            walletAddress,
-        });
-    }
-});
-
 // Platform Statistics endpoint
 server.get('/stats', (req, res) => {
     // Calculate stats from activeMarkets
@@ -1256,6 +1233,7 @@ server.post('/schedule-resolution/:marketId', async (req, res) => {
                     factConfidences: [],
                     decision: null,
                     reasoning: '',
+                    confidence: 0,
                     logs: [],
                     evidenceUrls: [],
                     ipfsTranscriptCid: '',
@@ -1425,16 +1403,22 @@ server.get('/oracle-stakes/:marketId', async (req, res) => {
         const stakes = await solanaAgent.getOracleStakesForMarket(marketPda);
 
         const totalStaked = stakes.reduce((sum, s) => sum + s.amount, 0);
+        const totalFor = stakes.filter(s => s.backsOracle).reduce((sum, s) => sum + s.amount, 0);
         const claimedCount = stakes.filter(s => s.claimed).length;
 
         res.json({
             marketId,
             totalStakes: stakes.length,
             totalStaked: totalStaked / 1_000_000, // Convert to Cred
+            totalFor: totalFor / 1_000_000,
+            totalAgainst: (totalStaked - totalFor) / 1_000_000,
+            // Market-implied probability that the agent's resolution stands
+            impliedAccuracy: totalStaked > 0 ? totalFor / totalStaked : null,
             claimedCount,
             stakes: stakes.map(s => ({
                 user: s.user.toBase58(),
                 amount: s.amount / 1_000_000,
+                backsOracle: s.backsOracle,
                 timestamp: s.timestamp,
                 claimed: s.claimed,
             })),
@@ -1513,6 +1497,7 @@ async function main() {
         factConfidences: [],
         decision: null,
         reasoning: '',
+        confidence: 0,
         logs: [],
         evidenceUrls: [],
         ipfsTranscriptCid: '',
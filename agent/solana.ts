@@ -32,6 +32,12 @@ const REPUTATION_VAULT_SEED = Buffer.from('reputation_vault');
 const CRED_STAKE_SEED = Buffer.from('cred_stake');
 const MARKET_SEED = Buffer.from('market');
 const ORACLE_STAKE_SEED = Buffer.from('oracle_stake');
+const PROTOCOL_CONFIG_SEED = Buffer.from('protocol_config');
+
+// Account discriminators: sha256("account:<Name>")[0..8]. CredStake and OracleStake
+// have matching field offsets, so size filters alone are fragile
+const CRED_STAKE_DISCRIMINATOR = Buffer.from([227, 133, 68, 177, 220, 64, 88, 60]);
+const ORACLE_STAKE_DISCRIMINATOR = Buffer.from([4, 129, 7, 149, 153, 172, 197, 166]);
 
 // Types
 export interface ResolveMarketParams {
@@ -39,6 +45,11 @@ export interface ResolveMarketParams {
     marketId: string;
     outcome: number; // 0 = No, 1 = Yes
     ipfsTranscriptHash: Uint8Array;
+    confidenceBps: number; // 0-10000
+    modelId: string;
+    researcherHash: Uint8Array; // SHA-256 of the researcher node's output
+    judgeHash: Uint8Array; // SHA-256 of the judge node's output
+    settlementBps?: number | null; // Partial-truth settlement, share of the pool paid to YES
 }
 
 export interface DistributeRewardsParams {
@@ -140,6 +151,16 @@ export class SolanaAgent {
         );
     }
 
+    /**
+     * Find the ProtocolConfig PDA
+     */
+    findProtocolConfigPda(): [PublicKey, number] {
+        return PublicKey.findProgramAddressSync(
+            [PROTOCOL_CONFIG_SEED],
+            PROPHECY_PROGRAM_ID
+        );
+    }
+
     /**
      * Find the InsightPool PDA
      */
@@ -189,12 +210,22 @@ export class SolanaAgent {
         console.log(`   IPFS Hash: ${Buffer.from(params.ipfsTranscriptHash).toString('hex').substring(0, 16)}...`);
 
         try {
-            const [agentExecutorPda] = this.findAgentExecutorPda();
-
             if (this.program) {
+                // The market is resolved by the executor its creator chose
+                const market = await this.program.account.market.fetch(params.marketPda);
+                const agentExecutorPda = market.agentExecutor as PublicKey;
+
                 // Real on-chain transaction
                 const tx = await this.program.methods
-                    .resolveMarket(params.outcome, Array.from(params.ipfsTranscriptHash))
+                    .resolveMarket(
+                        params.outcome,
+                        Array.from(params.ipfsTranscriptHash),
+                        params.confidenceBps,
+                        params.modelId,
+                        Array.from(params.researcherHash),
+                        Array.from(params.judgeHash),
+                        params.settlementBps ?? null
+                    )
                     .accounts({
                         market: params.marketPda,
                         agentExecutor: agentExecutorPda,
@@ -370,7 +401,8 @@ export class SolanaAgent {
     }
 
     /**
-     * Grant Cred to a user's ReputationVault (faucet functionality)
+     * Grant Cred to a user's ReputationVault via earn_cred. Not exposed to users - they
+     * top up low vaults themselves by signing claim_faucet.
      */
     async earnCred(
         recipientAddress: string,
@@ -436,6 +468,7 @@ export class SolanaAgent {
         amount: number;
         direction: boolean;
         timestamp: number;
+        claimed: boolean;
         bump: number;
     }>> {
         try {
@@ -448,12 +481,18 @@ export class SolanaAgent {
             console.log(`🔍 Querying CredStake accounts for market: ${marketPda.toBase58()}`);
 
             // Use getProgramAccounts with memcmp filter on market pubkey
-            // CredStake layout: user (32) + market (32 - offset 32) + amount (8) + direction (1) + timestamp (8) + bump (1)
+            // CredStake layout: user (32) + market (32 - offset 32) + amount (8) + direction (1) + timestamp (8) + claimed (1) + compensated (1) + sponsor_claimed (1) + bump (1)
             const accounts = await this.connection.getProgramAccounts(PROPHECY_PROGRAM_ID, {
                 filters: [
                     {
-                        // Filter by account data size (discriminator 8 + user 32 + market 32 + amount 8 + direction 1 + timestamp 8 + bump 1 = 90)
-                        dataSize: 90,
+                        // Filter by account data size (discriminator 8 + user 32 + market 32 + amount 8 + direction 1 + timestamp 8 + claimed 1 + compensated 1 + sponsor_claimed 1 + bump 1 = 93)
+                        dataSize: 93,
+                    },
+                    {
+                        memcmp: {
+                            offset: 0,
+                            bytes: bs58.encode(CRED_STAKE_DISCRIMINATOR),
+                        },
                     },
                     {
                         // Filter by market pubkey at offset 40 (8 discriminator + 32 user)
@@ -476,7 +515,8 @@ export class SolanaAgent {
                 const amount = Number(data.readBigUInt64LE(72));
                 const direction = data[80] === 1;
                 const timestamp = Number(data.readBigInt64LE(81));
-                const bump = data[89];
+                const claimed = data[89] === 1;
+                const bump = data[92];
 
                 return {
                     pubkey,
@@ -485,6 +525,7 @@ export class SolanaAgent {
                     amount,
                     direction,
                     timestamp,
+                    claimed,
                     bump,
                 };
             });
@@ -496,6 +537,35 @@ export class SolanaAgent {
         }
     }
 
+    /**
+     * Open the next InsightPool emission epoch if the current one has elapsed.
+     * Permissionless; fails harmlessly with EmissionNotDue mid-epoch.
+     */
+    async crankEmission(): Promise<TransactionResult> {
+        try {
+            await this.initProgram();
+
+            if (!this.program) {
+                throw new Error('Program not initialized');
+            }
+
+            const [insightPoolPda] = this.findInsightPoolPda();
+
+            const tx = await (this.program.methods as any)
+                .crankEmission()
+                .accounts({
+                    insightPool: insightPoolPda,
+                })
+                .signers([this.keypair])
+                .rpc();
+
+            console.log(`✅ Emission epoch opened! Signature: ${tx}`);
+            return { success: true, signature: tx };
+        } catch (error: any) {
+            return { success: false, error: error.message };
+        }
+    }
+
     /**
      * Distribute reward to a winning staker using distribute_insight_rewards instruction
      * Reference: Anchor program instruction
@@ -583,6 +653,9 @@ export class SolanaAgent {
         console.log(`   Total stakes: ${stakes.length}`);
         console.log(`   Winning stakes: ${winningStakes.length}`);
 
+        // Make sure the InsightPool has a budget for the current epoch
+        await this.crankEmission();
+
         let distributed = 0;
         let failed = 0;
 
@@ -682,6 +755,7 @@ export class SolanaAgent {
         user: PublicKey;
         market: PublicKey;
         amount: number;
+        backsOracle: boolean;
         timestamp: number;
         claimed: boolean;
         bump: number;
@@ -691,10 +765,11 @@ export class SolanaAgent {
 
             console.log(`🔍 Querying OracleStake accounts for market: ${marketPda.toBase58()}`);
 
-            // OracleStake layout: discriminator(8) + user(32) + market(32) + amount(8) + timestamp(8) + claimed(1) + bump(1) = 90
+            // OracleStake layout: discriminator(8) + user(32) + market(32) + amount(8) + backs_oracle(1) + timestamp(8) + claimed(1) + bump(1) = 91
             const accounts = await this.connection.getProgramAccounts(PROPHECY_PROGRAM_ID, {
                 filters: [
-                    { dataSize: 90 },
+                    { dataSize: 91 },
+                    { memcmp: { offset: 0, bytes: bs58.encode(ORACLE_STAKE_DISCRIMINATOR) } },
                     {
                         memcmp: {
                             offset: 40, // 8 discriminator + 32 user
@@ -711,11 +786,12 @@ export class SolanaAgent {
                 const user = new PublicKey(data.slice(8, 40));
                 const market = new PublicKey(data.slice(40, 72));
                 const amount = Number(data.readBigUInt64LE(72));
-                const timestamp = Number(data.readBigInt64LE(80));
-                const claimed = data[88] === 1;
-                const bump = data[89];
+                const backsOracle = data[80] === 1;
+                const timestamp = Number(data.readBigInt64LE(81));
+                const claimed = data[89] === 1;
+                const bump = data[90];
 
-                return { pubkey, user, market, amount, timestamp, claimed, bump };
+                return { pubkey, user, market, amount, backsOracle, timestamp, claimed, bump };
             });
 
             return stakes;
@@ -726,14 +802,13 @@ export class SolanaAgent {
     }
 
     /**
-     * Resolve all oracle stakes for a market
+     * Resolve all oracle stakes for a market. The program derives each outcome from the
+     * market's dispute state, so this only succeeds once the market is final.
      */
     async resolveAllOracleStakes(
-        marketPda: PublicKey,
-        marketWasDisputed: boolean
+        marketPda: PublicKey
     ): Promise<{ resolved: number; failed: number; total: number }> {
         console.log(`🎯 Resolving oracle stakes for market: ${marketPda.toBase58()}`);
-        console.log(`   Market was disputed: ${marketWasDisputed}`);
 
         const stakes = await this.getOracleStakesForMarket(marketPda);
         const unclaimedStakes = stakes.filter(s => !s.claimed);
@@ -752,17 +827,15 @@ export class SolanaAgent {
                     throw new Error('Program not initialized');
                 }
 
-                const [agentExecutorPda] = this.findAgentExecutorPda();
                 const [vaultPda] = this.findReputationVaultPda(stake.user);
 
                 const tx = await (this.program.methods as any)
-                    .resolveOracleStake(marketWasDisputed)
+                    .resolveOracleStake()
                     .accounts({
                         market: marketPda,
                         oracleStake: stake.pubkey,
                         reputationVault: vaultPda,
-                        agentExecutor: agentExecutorPda,
-                        authority: this.keypair.publicKey,
+                        protocolConfig: this.findProtocolConfigPda()[0],
                     })
                     .signers([this.keypair])
                     .rpc();
@@ -774,7 +847,6 @@ export class SolanaAgent {
                     market: marketPda.toBase58(),
                     user: stake.user.toBase58(),
                     amount: stake.amount,
-                    won: !marketWasDisputed,
                     signature: tx,
                 });
 
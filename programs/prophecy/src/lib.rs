@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_interface::{
+    self, spl_token_2022::extension::ExtensionType, Burn, CloseAccount, InitializeMint2, Mint,
+    MintTo, NonTransferableMintInitialize, TokenAccount, TokenInterface, TransferChecked,
+};
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
 
 declare_id!("UJW3ZdLcVxYuYDRpy6suu2DHCQhkUgCGKPUaDqdzSs4");
 
@@ -9,8 +16,63 @@ declare_id!("UJW3ZdLcVxYuYDRpy6suu2DHCQhkUgCGKPUaDqdzSs4");
 pub const MAX_TWEET_URL_LEN: usize = 280;
 pub const MAX_IPFS_CID_LEN: usize = 64;
 pub const MAX_EVIDENCE_COUNT: u8 = 10;
+pub const MAX_MEMO_LEN: usize = 140;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+pub const MAX_SPONSOR_NAME_LEN: usize = 64;
+pub const MAX_ENSEMBLE_SIZE: usize = 7;
+pub const ENSEMBLE_MODEL_ID: &str = "ensemble";
+pub const VERDICT_DOMAIN: &[u8] = b"prophecy:verdict:v1"; // Prefixed to signed verdict messages
 pub const CRED_DECIMALS: u8 = 6;
 pub const INITIAL_CRED_GRANT: u64 = 100_000_000; // 100 Cred with 6 decimals
+pub const DEFAULT_DAILY_TRANSFER_LIMIT: u64 = 50_000_000; // 50 Cred per vault per day
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const DEFAULT_REFERRAL_BONUS: u64 = 10_000_000; // 10 Cred
+pub const DEFAULT_REFERRAL_MIN_STAKE: u64 = 5_000_000; // Referee stake that must settle before the bonus pays
+pub const DEFAULT_MAX_REFERRALS: u32 = 25;
+pub const DEFAULT_UNATTESTED_GRANT: u64 = 20_000_000; // 20 Cred up front without an attestation
+pub const DEFAULT_GRANT_TRANCHE: u64 = 20_000_000; // Max unlocked per settled stake
+pub const DEFAULT_IDENTITY_REWARD: u64 = 25_000_000; // 25 Cred, paid once per attested user
+pub const DEFAULT_FAUCET_AMOUNT: u64 = 25_000_000;
+pub const DEFAULT_FAUCET_COOLDOWN: i64 = SECONDS_PER_DAY;
+pub const DEFAULT_FAUCET_EPOCH_DURATION: i64 = 7 * SECONDS_PER_DAY;
+pub const DEFAULT_FAUCET_EPOCH_BUDGET: u64 = 10_000_000_000; // 10,000 Cred per epoch
+pub const DEFAULT_FAUCET_BALANCE_THRESHOLD: u64 = 50_000_000; // Only vaults under 50 Cred can claim
+pub const EARN_METHOD_COUNT: usize = 7;
+/// Default per-vault daily earn_cred caps, indexed by EarnMethod
+pub const DEFAULT_EARN_METHOD_DAILY_CAPS: [u64; EARN_METHOD_COUNT] = [
+    0,           // InitialGrant - only paid by vault creation
+    50_000_000,  // EvidenceSubmission
+    200_000_000, // CorrectPrediction
+    0,           // Referral - only paid by claim_cred_stake / claim_referral_bonus
+    0,           // IdentityVerification - only paid by claim_identity_reward
+    100_000_000, // CommunityContribution
+    0,           // Faucet - only paid by claim_faucet
+];
+pub const DEFAULT_DAILY_MINT_CEILING: u64 = 100_000_000_000; // 100,000 Cred per day across all vaults
+pub const DEFAULT_EMISSION_PER_EPOCH: u64 = 5_000_000_000; // 5,000 Cred in the first InsightPool epoch
+pub const DEFAULT_EMISSION_DECAY_BPS: u16 = 200; // Each epoch emits 2% less than the previous one
+pub const DEFAULT_EMISSION_EPOCH_DURATION: i64 = 7 * SECONDS_PER_DAY;
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 2 * SECONDS_PER_DAY; // A resolution is final once this passes undisputed
+pub const DEFAULT_ORACLE_HOUSE_EDGE_BPS: u16 = 500; // 5% of oracle stake winnings go to the InsightPool
+pub const DEFAULT_EXECUTOR_MIN_BOND: u64 = 0; // Bond required to resolve markets; 0 disables the check
+pub const DEFAULT_EXECUTOR_SLASH_BPS: u16 = 2_000; // 20% of the bond is slashed per overturned resolution
+pub const DEFAULT_DISPUTER_SLASH_SHARE_BPS: u16 = 2_500; // Disputer's share of a slash; the rest goes to stakers
+pub const DEFAULT_MARKET_MAX_AGE: i64 = 30 * SECONDS_PER_DAY; // Unresolved markets can be voided after this
+pub const DEFAULT_EXPIRY_CRANK_REWARD: u64 = 1_000_000; // 1 Cred to whoever voids an expired market
+pub const DEFAULT_MARKET_POOL_CLAIM_WINDOW: i64 = 30 * SECONDS_PER_DAY; // Then unclaimed market pool Cred is swept
+pub const MAX_MODEL_ID_LEN: usize = 32;
+pub const BPS_DENOMINATOR: u16 = 10_000;
+pub const FULL_REWARD_CONFIDENCE_BPS: u16 = 7_000; // Below this, rewards scale down linearly to 0
+
+// Market outcomes
+pub const OUTCOME_NO: u8 = 0;
+pub const OUTCOME_YES: u8 = 1;
+pub const OUTCOME_INVALID: u8 = 2; // Unverifiable claim - all Cred stakes are refunded
+
+// Leaderboard
+pub const LEADERBOARD_SIZE: usize = 20;
+pub const MIN_SETTLED_FOR_RANKING: u64 = 5; // Settled stakes required before accuracy/calibration rank
+pub const SEASON_WINNERS_COUNT: usize = 3;
 
 // PDA Seeds
 pub const INSIGHT_POOL_SEED: &[u8] = b"insight_pool";
@@ -19,6 +81,21 @@ pub const REPUTATION_VAULT_SEED: &[u8] = b"reputation_vault";
 pub const CRED_STAKE_SEED: &[u8] = b"cred_stake";
 pub const MARKET_SEED: &[u8] = b"market";
 pub const ORACLE_STAKE_SEED: &[u8] = b"oracle_stake";
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+pub const SEASON_SEED: &[u8] = b"season";
+pub const SEASON_STATS_SEED: &[u8] = b"season_stats";
+pub const CRED_MINT_SEED: &[u8] = b"cred_mint";
+pub const MARKET_ESCROW_SEED: &[u8] = b"market_escrow";
+pub const EVIDENCE_SEED: &[u8] = b"evidence";
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+pub const ATTESTER_SEED: &[u8] = b"attester";
+pub const FAUCET_SEED: &[u8] = b"faucet";
+pub const CRED_LEDGER_SEED: &[u8] = b"cred_ledger";
+pub const VERDICT_SEED: &[u8] = b"verdict";
+pub const SPONSOR_ESCROW_SEED: &[u8] = b"sponsor_escrow";
+pub const SPONSOR_VAULT_SEED: &[u8] = b"sponsor_vault";
+pub const MARKET_BOOST_SEED: &[u8] = b"market_boost";
 
 // ============================================================================
 // PROGRAM
@@ -35,52 +112,697 @@ pub mod prophecy {
         pool.distributions_count = 0;
         pool.last_distribution = Clock::get()?.unix_timestamp;
         pool.authority = ctx.accounts.authority.key();
+        pool.epoch = 0;
+        pool.epoch_start = 0;
+        pool.epoch_budget = 0;
+        pool.remaining_budget = 0;
+        pool.total_emitted = 0;
+        pool.house_edge_collected = 0;
+        pool.market_pools_swept = 0;
         pool.bump = ctx.bumps.insight_pool;
 
         msg!("InsightPool initialized");
         Ok(())
     }
 
-    /// Initialize the AgentExecutor authority PDA
+    /// Initialize the default AgentExecutor authority PDA
     pub fn initialize_agent_executor(ctx: Context<InitializeAgentExecutor>) -> Result<()> {
         let executor = &mut ctx.accounts.agent_executor;
         executor.authority = ctx.accounts.authority.key();
+        executor.metadata_uri = String::new();
+        executor.active = true;
+        executor.registered_at = Clock::get()?.unix_timestamp;
         executor.markets_resolved = 0;
+        executor.resolutions_upheld = 0;
+        executor.resolutions_overturned = 0;
+        executor.bond = 0;
+        executor.total_slashed = 0;
         executor.bump = ctx.bumps.agent_executor;
 
         msg!("AgentExecutor initialized with authority: {}", executor.authority);
         Ok(())
     }
 
-    /// Initialize a ReputationVault for a user - grants initial Cred
+    /// Register an additional resolver (e.g. another model or a human-curated panel) that
+    /// market creators can choose - ONLY callable by the config authority
+    pub fn register_executor(
+        ctx: Context<RegisterExecutor>,
+        authority: Pubkey,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.config_authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+
+        let executor = &mut ctx.accounts.agent_executor;
+        executor.authority = authority;
+        executor.markets_resolved = 0;
+        executor.resolutions_upheld = 0;
+        executor.resolutions_overturned = 0;
+        executor.bond = 0;
+        executor.total_slashed = 0;
+        executor.metadata_uri = metadata_uri.clone();
+        executor.active = true;
+        executor.registered_at = Clock::get()?.unix_timestamp;
+        executor.bump = ctx.bumps.agent_executor;
+
+        emit!(ExecutorRegistered {
+            executor: executor.key(),
+            authority,
+            metadata_uri,
+            timestamp: executor.registered_at,
+        });
+
+        msg!("Executor registered with authority: {}", authority);
+        Ok(())
+    }
+
+    /// Enable or disable an executor for new markets and resolutions - ONLY callable by the config authority
+    pub fn set_executor_active(ctx: Context<SetExecutorActive>, active: bool) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let executor = &mut ctx.accounts.agent_executor;
+        executor.active = active;
+
+        msg!("Executor {} active: {}", executor.authority, active);
+        Ok(())
+    }
+
+    /// Initialize the global ProtocolConfig with default settings
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        config.authority = ctx.accounts.authority.key();
+        config.invalid_oracle_policy = InvalidOraclePolicy::StakersLose;
+        config.current_season = 0;
+        config.cred_mint = None;
+        config.cred_mint_bump = 0;
+        config.cred_non_transferable = false;
+        config.daily_transfer_limit = DEFAULT_DAILY_TRANSFER_LIMIT;
+        config.referral_bonus = DEFAULT_REFERRAL_BONUS;
+        config.referral_min_stake = DEFAULT_REFERRAL_MIN_STAKE;
+        config.max_referrals_per_referrer = DEFAULT_MAX_REFERRALS;
+        config.unattested_grant = DEFAULT_UNATTESTED_GRANT;
+        config.grant_tranche = DEFAULT_GRANT_TRANCHE;
+        config.identity_reward = DEFAULT_IDENTITY_REWARD;
+        config.require_attestation_for_markets = false;
+        config.require_attestation_for_disputes = false;
+        config.faucet_amount = DEFAULT_FAUCET_AMOUNT;
+        config.faucet_cooldown = DEFAULT_FAUCET_COOLDOWN;
+        config.faucet_epoch_duration = DEFAULT_FAUCET_EPOCH_DURATION;
+        config.faucet_epoch_budget = DEFAULT_FAUCET_EPOCH_BUDGET;
+        config.faucet_balance_threshold = DEFAULT_FAUCET_BALANCE_THRESHOLD;
+        config.earn_method_daily_caps = DEFAULT_EARN_METHOD_DAILY_CAPS;
+        config.daily_mint_ceiling = DEFAULT_DAILY_MINT_CEILING;
+        config.mint_day = 0;
+        config.minted_today = 0;
+        config.emission_per_epoch = DEFAULT_EMISSION_PER_EPOCH;
+        config.emission_decay_bps = DEFAULT_EMISSION_DECAY_BPS;
+        config.emission_epoch_duration = DEFAULT_EMISSION_EPOCH_DURATION;
+        config.dispute_window = DEFAULT_DISPUTE_WINDOW;
+        config.oracle_house_edge_bps = DEFAULT_ORACLE_HOUSE_EDGE_BPS;
+        config.executor_min_bond = DEFAULT_EXECUTOR_MIN_BOND;
+        config.executor_slash_bps = DEFAULT_EXECUTOR_SLASH_BPS;
+        config.disputer_slash_share_bps = DEFAULT_DISPUTER_SLASH_SHARE_BPS;
+        config.market_max_age = DEFAULT_MARKET_MAX_AGE;
+        config.expiry_crank_reward = DEFAULT_EXPIRY_CRANK_REWARD;
+        config.market_pool_claim_window = DEFAULT_MARKET_POOL_CLAIM_WINDOW;
+        config.bump = ctx.bumps.protocol_config;
+
+        msg!("ProtocolConfig initialized with authority: {}", config.authority);
+        Ok(())
+    }
+
+    /// Update protocol settings - only fields that are set are changed
+    pub fn update_protocol_config(
+        ctx: Context<UpdateProtocolConfig>,
+        update: ProtocolConfigUpdate,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        if let Some(policy) = update.invalid_oracle_policy {
+            config.invalid_oracle_policy = policy;
+        }
+        if let Some(limit) = update.daily_transfer_limit {
+            config.daily_transfer_limit = limit;
+        }
+        if let Some(bonus) = update.referral_bonus {
+            config.referral_bonus = bonus;
+        }
+        if let Some(min_stake) = update.referral_min_stake {
+            config.referral_min_stake = min_stake;
+        }
+        if let Some(max) = update.max_referrals_per_referrer {
+            config.max_referrals_per_referrer = max;
+        }
+        if let Some(grant) = update.unattested_grant {
+            require!(grant <= INITIAL_CRED_GRANT, ErrorCode::InvalidAmount);
+            config.unattested_grant = grant;
+        }
+        if let Some(tranche) = update.grant_tranche {
+            require!(tranche > 0, ErrorCode::InvalidAmount);
+            config.grant_tranche = tranche;
+        }
+        if let Some(reward) = update.identity_reward {
+            config.identity_reward = reward;
+        }
+        if let Some(required) = update.require_attestation_for_markets {
+            config.require_attestation_for_markets = required;
+        }
+        if let Some(required) = update.require_attestation_for_disputes {
+            config.require_attestation_for_disputes = required;
+        }
+        if let Some(amount) = update.faucet_amount {
+            config.faucet_amount = amount;
+        }
+        if let Some(cooldown) = update.faucet_cooldown {
+            require!(cooldown >= 0, ErrorCode::InvalidFaucetConfig);
+            config.faucet_cooldown = cooldown;
+        }
+        if let Some(duration) = update.faucet_epoch_duration {
+            require!(duration > 0, ErrorCode::InvalidFaucetConfig);
+            config.faucet_epoch_duration = duration;
+        }
+        if let Some(budget) = update.faucet_epoch_budget {
+            config.faucet_epoch_budget = budget;
+        }
+        if let Some(threshold) = update.faucet_balance_threshold {
+            config.faucet_balance_threshold = threshold;
+        }
+        if let Some(caps) = update.earn_method_daily_caps {
+            config.earn_method_daily_caps = caps;
+        }
+        if let Some(ceiling) = update.daily_mint_ceiling {
+            config.daily_mint_ceiling = ceiling;
+        }
+        if let Some(amount) = update.emission_per_epoch {
+            config.emission_per_epoch = amount;
+        }
+        if let Some(decay) = update.emission_decay_bps {
+            require!(decay <= BPS_DENOMINATOR, ErrorCode::InvalidEmissionConfig);
+            config.emission_decay_bps = decay;
+        }
+        if let Some(duration) = update.emission_epoch_duration {
+            require!(duration > 0, ErrorCode::InvalidEmissionConfig);
+            config.emission_epoch_duration = duration;
+        }
+        if let Some(window) = update.dispute_window {
+            require!(window >= 0, ErrorCode::InvalidDisputeWindow);
+            config.dispute_window = window;
+        }
+        if let Some(edge) = update.oracle_house_edge_bps {
+            require!(edge <= BPS_DENOMINATOR, ErrorCode::InvalidHouseEdge);
+            config.oracle_house_edge_bps = edge;
+        }
+        if let Some(min_bond) = update.executor_min_bond {
+            config.executor_min_bond = min_bond;
+        }
+        if let Some(slash) = update.executor_slash_bps {
+            require!(slash <= BPS_DENOMINATOR, ErrorCode::InvalidSlashConfig);
+            config.executor_slash_bps = slash;
+        }
+        if let Some(share) = update.disputer_slash_share_bps {
+            require!(share <= BPS_DENOMINATOR, ErrorCode::InvalidSlashConfig);
+            config.disputer_slash_share_bps = share;
+        }
+        if let Some(max_age) = update.market_max_age {
+            require!(max_age > 0, ErrorCode::InvalidMarketMaxAge);
+            config.market_max_age = max_age;
+        }
+        if let Some(reward) = update.expiry_crank_reward {
+            config.expiry_crank_reward = reward;
+        }
+        if let Some(window) = update.market_pool_claim_window {
+            require!(window >= 0, ErrorCode::InvalidClaimWindow);
+            config.market_pool_claim_window = window;
+        }
+
+        msg!("ProtocolConfig updated");
+        Ok(())
+    }
+
+    /// Switch Cred to token mode by creating the Cred SPL mint, controlled by its own PDA.
+    /// With `non_transferable` the mint uses the Token-2022 NonTransferable extension.
+    /// Cred that already exists is backed afterwards: vaults by `migrate_vault_to_token`
+    /// and open market stakes by `initialize_market_escrow`.
+    pub fn initialize_cred_mint(ctx: Context<InitializeCredMint>, non_transferable: bool) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+        require!(config.cred_mint.is_none(), ErrorCode::CredMintAlreadyInitialized);
+        if non_transferable {
+            require_keys_eq!(
+                ctx.accounts.token_program.key(),
+                anchor_spl::token_2022::ID,
+                ErrorCode::InvalidCredMint
+            );
+        }
+
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let mint_info = ctx.accounts.cred_mint.to_account_info();
+        let extensions = vec![ExtensionType::NonTransferable];
+        let space = token_interface::find_mint_account_size(non_transferable.then_some(&extensions))?;
+        let bump = ctx.bumps.cred_mint;
+        let signer_seeds: &[&[&[u8]]] = &[&[CRED_MINT_SEED, &[bump]]];
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: mint_info.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            token_program.key,
+        )?;
+
+        if non_transferable {
+            token_interface::non_transferable_mint_initialize(CpiContext::new(
+                token_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: token_program.clone(),
+                    mint: mint_info.clone(),
+                },
+            ))?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(token_program, InitializeMint2 { mint: mint_info.clone() }),
+            CRED_DECIMALS,
+            mint_info.key,
+            None,
+        )?;
+
+        config.cred_mint = Some(mint_info.key());
+        config.cred_mint_bump = bump;
+        config.cred_non_transferable = non_transferable;
+
+        msg!("Cred mint initialized: {} (non-transferable: {})", mint_info.key(), non_transferable);
+        Ok(())
+    }
+
+    /// Create a market's Cred escrow token account (token mode, permissionless). Stakes
+    /// placed before token mode that are still held by the market are minted into it.
+    pub fn initialize_market_escrow(ctx: Context<InitializeMarketEscrow>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let outstanding = market
+            .total_yes_stake
+            .checked_add(market.total_no_stake)
+            .and_then(|v| v.checked_sub(market.total_paid_out))
+            .and_then(|v| v.checked_sub(market.total_refunded))
+            .ok_or(ErrorCode::Overflow)?;
+
+        if outstanding > 0 {
+            let token = CredToken::load(
+                &ctx.accounts.protocol_config,
+                Some(&ctx.accounts.cred_mint),
+                Some(&ctx.accounts.token_program),
+            )?
+            .ok_or(ErrorCode::InvalidCredMint)?;
+            token.mint_to(&ctx.accounts.market_escrow, outstanding)?;
+        }
+
+        msg!("Cred escrow created for market {} holding {}", market.key(), outstanding);
+        Ok(())
+    }
+
+    /// Back a vault created before token mode with Cred tokens (permissionless). Its
+    /// balance is minted to the owner's token account once; until then the vault cannot
+    /// take part in anything that moves Cred tokens. The ledger already counts this Cred.
+    pub fn migrate_vault_to_token(ctx: Context<MigrateVaultToToken>) -> Result<()> {
+        let token = CredToken::load(
+            &ctx.accounts.protocol_config,
+            Some(&ctx.accounts.cred_mint),
+            Some(&ctx.accounts.token_program),
+        )?
+        .ok_or(ErrorCode::InvalidCredMint)?;
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        require!(!vault.token_backed, ErrorCode::VaultAlreadyTokenBacked);
+        vault.token_backed = true;
+
+        let cred_account = token.vault_account(Some(&ctx.accounts.cred_account), vault)?;
+        if vault.cred_balance > 0 {
+            token.mint_to(cred_account, vault.cred_balance)?;
+        }
+
+        emit!(VaultMigratedToToken {
+            user: vault.owner,
+            amount: vault.cred_balance,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Minted {} Cred tokens to back the vault of {}", vault.cred_balance, vault.owner);
+        Ok(())
+    }
+
+    /// Approve an attester (e.g. the Twitter verification bot) - ONLY callable by the config authority
+    pub fn add_attester(ctx: Context<AddAttester>, attester: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let registry = &mut ctx.accounts.attester_account;
+        registry.attester = attester;
+        registry.active = true;
+        registry.attestations_issued = 0;
+        registry.added_at = Clock::get()?.unix_timestamp;
+        registry.bump = ctx.bumps.attester_account;
+
+        msg!("Attester added: {}", attester);
+        Ok(())
+    }
+
+    /// Enable or disable an approved attester - ONLY callable by the config authority.
+    /// Attestations it already issued stay valid until they expire.
+    pub fn set_attester_active(ctx: Context<SetAttesterActive>, active: bool) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let registry = &mut ctx.accounts.attester_account;
+        registry.active = active;
+
+        msg!("Attester {} active: {}", registry.attester, active);
+        Ok(())
+    }
+
+    /// Issue an identity attestation for a user - ONLY callable by an active attester
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        attestation_type: AttestationType,
+        handle_hash: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.attester_account;
+        require!(registry.active, ErrorCode::AttesterInactive);
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at == 0 || expires_at > now, ErrorCode::InvalidAttestationExpiry);
+        registry.attestations_issued = registry.attestations_issued.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.subject = ctx.accounts.subject.key();
+        attestation.attester = ctx.accounts.attester.key();
+        attestation.attestation_type = attestation_type;
+        attestation.handle_hash = handle_hash;
+        attestation.issued_at = now;
+        attestation.expires_at = expires_at;
+        attestation.rewarded = false;
+        attestation.bump = ctx.bumps.attestation;
+
+        emit!(AttestationIssued {
+            subject: attestation.subject,
+            attester: attestation.attester,
+            attestation_type,
+            expires_at,
+            timestamp: now,
+        });
+
+        msg!("Attestation issued for {}", attestation.subject);
+        Ok(())
+    }
+
+    /// Extend or shorten one of your attestations' expiry - ONLY callable by its active attester
+    pub fn renew_attestation(ctx: Context<RenewAttestation>, expires_at: i64) -> Result<()> {
+        require!(ctx.accounts.attester_account.active, ErrorCode::AttesterInactive);
+        let now = Clock::get()?.unix_timestamp;
+        require!(expires_at == 0 || expires_at > now, ErrorCode::InvalidAttestationExpiry);
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.expires_at = expires_at;
+
+        emit!(AttestationRenewed {
+            subject: attestation.subject,
+            attester: attestation.attester,
+            expires_at,
+            timestamp: now,
+        });
+
+        msg!("Attestation renewed for {}", attestation.subject);
+        Ok(())
+    }
+
+    /// Revoke one of your attestations, closing it and refunding its rent - ONLY callable
+    /// by its attester, even after the attester is deactivated
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        let attestation = &ctx.accounts.attestation;
+
+        emit!(AttestationRevoked {
+            subject: attestation.subject,
+            attester: attestation.attester,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Attestation revoked for {}", attestation.subject);
+        Ok(())
+    }
+
+    /// Award IdentityVerification Cred for a valid attestation - paid once per user (permissionless)
+    pub fn claim_identity_reward(ctx: Context<ClaimIdentityReward>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let attestation = &mut ctx.accounts.attestation;
+        require!(attestation.is_valid(now), ErrorCode::AttestationRequired);
+        require!(!attestation.rewarded, ErrorCode::IdentityRewardAlreadyClaimed);
+        attestation.rewarded = true;
+
+        // A user can hold attestations from several attesters, or be re-attested after a
+        // revocation, so the reward is tracked on the vault as well
+        let vault = &mut ctx.accounts.reputation_vault;
+        require!(
+            vault.earned_by_method[EarnMethod::IdentityVerification as usize] == 0,
+            ErrorCode::IdentityRewardAlreadyClaimed
+        );
+
+        let amount = ctx.accounts.protocol_config.identity_reward;
+        vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        vault.total_earned = vault.total_earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, amount)?;
+        }
+
+        vault.record_earned(EarnMethod::IdentityVerification, amount)?;
+        ctx.accounts.cred_ledger.record_mint(amount)?;
+
+        emit!(CredEarned {
+            user: vault.owner,
+            amount,
+            method: EarnMethod::IdentityVerification,
+            timestamp: now,
+        });
+
+        msg!("Identity verification reward of {} Cred paid to {}", amount, vault.owner);
+        Ok(())
+    }
+
+    /// Initialize a ReputationVault for a user - grants initial Cred.
+    /// The full grant requires the owner's signature and a valid attestation; otherwise
+    /// a reduced grant is paid and the rest unlocks in tranches as stakes settle.
+    /// Passing `referrer_vault` records who referred this user.
     pub fn initialize_reputation_vault(ctx: Context<InitializeReputationVault>) -> Result<()> {
+        let config = &ctx.accounts.protocol_config;
+        let now = Clock::get()?.unix_timestamp;
+        let attested = ctx.accounts.owner.is_signer
+            && ctx
+                .accounts
+                .attestation
+                .as_ref()
+                .is_some_and(|attestation| attestation.is_valid(now));
+        let unlocked = if attested { INITIAL_CRED_GRANT } else { config.unattested_grant };
+
         let vault = &mut ctx.accounts.reputation_vault;
         vault.owner = ctx.accounts.owner.key();
-        vault.cred_balance = INITIAL_CRED_GRANT; // Grant initial Cred
-        vault.total_earned = INITIAL_CRED_GRANT;
+        vault.cred_balance = unlocked; // Grant initial Cred
+        vault.total_earned = unlocked;
+        vault.locked_grant = INITIAL_CRED_GRANT - unlocked;
+        vault.last_faucet_claim = 0;
+        vault.faucet_balance = 0;
+        vault.earn_day = 0;
+        vault.earned_today_by_method = [0; EARN_METHOD_COUNT];
+        vault.earned_by_method = [0; EARN_METHOD_COUNT];
         vault.total_staked = 0;
         vault.participation_count = 0;
+        vault.wins = 0;
+        vault.losses = 0;
+        vault.settled_count = 0;
+        vault.settled_stake = 0;
+        vault.brier_score_total = 0;
+        vault.transfer_day = 0;
+        vault.transferred_today = 0;
+        vault.referrer = None;
+        vault.referral_count = 0;
+        vault.referral_bonus_paid = false;
+        vault.token_backed = false;
         vault.bump = ctx.bumps.reputation_vault;
 
+        if let Some(referrer) = ctx.accounts.referrer_vault.as_mut() {
+            // Otherwise anyone could spend a referrer's cap on vaults they do not own
+            require!(ctx.accounts.owner.is_signer, ErrorCode::ReferralRequiresSignature);
+            require!(referrer.owner != vault.owner, ErrorCode::SelfReferral);
+            require!(
+                referrer.referral_count < ctx.accounts.protocol_config.max_referrals_per_referrer,
+                ErrorCode::ReferralCapReached
+            );
+            referrer.referral_count = referrer.referral_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+            vault.referrer = Some(referrer.owner);
+
+            emit!(ReferralRecorded {
+                referrer: referrer.owner,
+                referee: vault.owner,
+                timestamp: now,
+            });
+        }
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            vault.token_backed = true;
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, unlocked)?;
+        }
+
+        vault.record_earned(EarnMethod::InitialGrant, unlocked)?;
+        ctx.accounts.cred_ledger.record_mint(unlocked)?;
+
         emit!(CredEarned {
             user: vault.owner,
-            amount: INITIAL_CRED_GRANT,
+            amount: unlocked,
             method: EarnMethod::InitialGrant,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: now,
+        });
+
+        msg!("ReputationVault initialized for: {} ({} Cred locked)", vault.owner, vault.locked_grant);
+        Ok(())
+    }
+
+    /// Create the Cred faucet state - ONLY callable by the config authority
+    pub fn initialize_faucet(ctx: Context<InitializeFaucet>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let faucet = &mut ctx.accounts.faucet;
+        faucet.epoch = 0;
+        faucet.epoch_start = Clock::get()?.unix_timestamp;
+        faucet.distributed_in_epoch = 0;
+        faucet.total_distributed = 0;
+        faucet.claims = 0;
+        faucet.bump = ctx.bumps.faucet;
+
+        msg!("Cred faucet initialized");
+        Ok(())
+    }
+
+    /// Create the global Cred ledger - ONLY callable by the config authority.
+    /// Must exist before any vault is created, as every Cred flow updates it.
+    pub fn initialize_cred_ledger(ctx: Context<InitializeCredLedger>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let ledger = &mut ctx.accounts.cred_ledger;
+        ledger.total_minted = 0;
+        ledger.total_burned = 0;
+        ledger.total_staked = 0;
+        ledger.total_paid_out = 0;
+        ledger.total_refunded = 0;
+        ledger.bump = ctx.bumps.cred_ledger;
+
+        msg!("Cred ledger initialized");
+        Ok(())
+    }
+
+    /// Top up a low vault from the faucet - signed by the vault owner.
+    /// Limited by a per-vault cooldown, a balance threshold and a per-epoch global budget.
+    pub fn claim_faucet(ctx: Context<ClaimFaucet>) -> Result<()> {
+        let config = &ctx.accounts.protocol_config;
+        let now = Clock::get()?.unix_timestamp;
+        let amount = config.faucet_amount;
+        require!(amount > 0, ErrorCode::FaucetDisabled);
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        require!(vault.cred_balance < config.faucet_balance_threshold, ErrorCode::FaucetBalanceTooHigh);
+        require!(
+            vault.last_faucet_claim == 0 || now >= vault.last_faucet_claim.saturating_add(config.faucet_cooldown),
+            ErrorCode::FaucetCooldown
+        );
+
+        let faucet = &mut ctx.accounts.faucet;
+        faucet.roll_epoch(now, config.faucet_epoch_duration);
+        let distributed = faucet.distributed_in_epoch.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        require!(distributed <= config.faucet_epoch_budget, ErrorCode::FaucetBudgetExhausted);
+        faucet.distributed_in_epoch = distributed;
+        faucet.total_distributed = faucet.total_distributed.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        faucet.claims = faucet.claims.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        // Faucet Cred is a top-up rather than earnings, so it does not count toward total_earned.
+        // It can be staked but not transferred, so sybil vaults cannot pool it.
+        vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        vault.faucet_balance = vault.faucet_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        vault.last_faucet_claim = now;
+
+        if let Some(token) = CredToken::load(
+            config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, amount)?;
+        }
+
+        vault.record_earned(EarnMethod::Faucet, amount)?;
+        ctx.accounts.cred_ledger.record_mint(amount)?;
+
+        emit!(CredEarned {
+            user: vault.owner,
+            amount,
+            method: EarnMethod::Faucet,
+            timestamp: now,
         });
 
-        msg!("ReputationVault initialized for: {}", vault.owner);
+        msg!("Faucet paid {} Cred to {}", amount, vault.owner);
         Ok(())
     }
 
-    /// Create a new prediction market
+    /// Create a new prediction market. A `sponsor` reserves the market's sponsor
+    /// escrow for that key; without one anyone may sponsor it.
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         tweet_url: String,
         market_id: String,
+        sponsor: Option<Pubkey>,
     ) -> Result<()> {
         require!(tweet_url.len() <= MAX_TWEET_URL_LEN, ErrorCode::TweetUrlTooLong);
         require!(market_id.len() <= 32, ErrorCode::MarketIdTooLong);
+        require!(ctx.accounts.agent_executor.active, ErrorCode::ExecutorInactive);
+        require_attestation(
+            ctx.accounts.protocol_config.require_attestation_for_markets,
+            ctx.accounts.attestation.as_ref(),
+        )?;
 
         let market = &mut ctx.accounts.market;
         market.creator = ctx.accounts.creator.key();
@@ -90,11 +812,39 @@ pub mod prophecy {
         market.status = MarketStatus::Open;
         market.outcome = None;
         market.ipfs_transcript_hash = [0u8; 32];
+        market.confidence_bps = 0;
+        market.model_id = String::new();
+        market.researcher_hash = [0u8; 32];
+        market.judge_hash = [0u8; 32];
+        market.settlement_bps = 0;
+        market.resolved_at = 0;
+        market.dispute_status = DisputeStatus::None;
         market.insight_pool_amount = 0;
+        market.market_pool = 0;
+        market.market_pool_paid_out = 0;
+        market.market_pool_swept = false;
+        market.market_pool_swept_amount = 0;
+        market.market_pool_refunded = 0;
         market.agent_executor = ctx.accounts.agent_executor.key();
+        market.sponsor = sponsor;
         market.evidence_count = 0;
         market.total_yes_stake = 0;
         market.total_no_stake = 0;
+        market.oracle_for_stake = 0;
+        market.oracle_against_stake = 0;
+        market.disputer = Pubkey::default();
+        market.overturned_outcome = None;
+        market.slash_compensation = 0;
+        market.ensemble_quorum = 0;
+        market.ensemble_size = 0;
+        market.ensemble_executors = Vec::new();
+        market.verdict_nonce = 0;
+        market.verdict_count = 0;
+        market.verdict_tallies = [0; 3];
+        market.verdict_confidence_total = [0; 3];
+        market.total_paid_out = 0;
+        market.total_refunded = 0;
+        market.settled_stake = 0;
         market.bump = ctx.bumps.market;
 
         emit!(MarketCreated {
@@ -125,6 +875,7 @@ pub mod prophecy {
 
         // Deduct from vault
         vault.cred_balance = vault.cred_balance.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        vault.spend_faucet_first(amount);
         vault.total_staked = vault.total_staked.checked_add(amount).ok_or(ErrorCode::Overflow)?;
         vault.participation_count = vault.participation_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
 
@@ -135,8 +886,24 @@ pub mod prophecy {
         stake.amount = amount;
         stake.direction = direction;
         stake.timestamp = Clock::get()?.unix_timestamp;
+        stake.claimed = false;
+        stake.compensated = false;
+        stake.sponsor_claimed = false;
+        stake.insight_distributed = false;
         stake.bump = ctx.bumps.cred_stake;
 
+        // Token mode: move the staked Cred into the market escrow
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), &ctx.accounts.reputation_vault)?;
+            let escrow = token.escrow_account(ctx.accounts.market_escrow.as_deref(), &market.key())?;
+            token.transfer(cred_account, escrow, ctx.accounts.user.to_account_info(), &[], amount)?;
+        }
+        ctx.accounts.cred_ledger.record_stake(amount)?;
+
         // Update market totals
         if direction {
             market.total_yes_stake = market.total_yes_stake.checked_add(amount).ok_or(ErrorCode::Overflow)?;
@@ -169,12 +936,22 @@ pub mod prophecy {
 
         market.evidence_count = market.evidence_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
 
+        let clock = Clock::get()?;
+        let evidence = &mut ctx.accounts.evidence;
+        evidence.market = market.key();
+        evidence.submitter = ctx.accounts.user.key();
+        evidence.index = market.evidence_count;
+        evidence.ipfs_cid = ipfs_cid.clone();
+        evidence.tips_received = 0;
+        evidence.submitted_at = clock.unix_timestamp;
+        evidence.bump = ctx.bumps.evidence;
+
         emit!(EvidenceSubmitted {
             market: market.key(),
             user: ctx.accounts.user.key(),
             ipfs_cid,
             evidence_index: market.evidence_count,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: clock.unix_timestamp,
         });
 
         msg!("Evidence submitted to market: {}", market.key());
@@ -182,15 +959,28 @@ pub mod prophecy {
     }
 
     /// Resolve a market - ONLY callable by the AgentExecutor authority
+    #[allow(clippy::too_many_arguments)]
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
-        outcome: u8, // 0 = No, 1 = Yes
+        outcome: u8, // 0 = No, 1 = Yes, 2 = Invalid
         ipfs_transcript_hash: [u8; 32],
+        confidence_bps: u16,
+        model_id: String,
+        researcher_hash: [u8; 32],
+        judge_hash: [u8; 32],
+        settlement_bps: Option<u16>, // Partial-truth settlement: fraction of the pool paid to YES
     ) -> Result<()> {
-        require!(outcome <= 1, ErrorCode::InvalidOutcome);
+        require!(outcome <= OUTCOME_INVALID, ErrorCode::InvalidOutcome);
+        require!(confidence_bps <= BPS_DENOMINATOR, ErrorCode::InvalidConfidence);
+        require!(model_id.len() <= MAX_MODEL_ID_LEN, ErrorCode::ModelIdTooLong);
+        if let Some(bps) = settlement_bps {
+            require!(bps <= BPS_DENOMINATOR, ErrorCode::InvalidSettlement);
+            require!(outcome != OUTCOME_INVALID, ErrorCode::InvalidSettlement);
+        }
         
         let market = &mut ctx.accounts.market;
         require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(market.ensemble_quorum == 0, ErrorCode::EnsembleMarket);
         
         // Verify the signer is the agent executor authority
         let executor = &ctx.accounts.agent_executor;
@@ -198,455 +988,4303 @@ pub mod prophecy {
             ctx.accounts.authority.key() == executor.authority,
             ErrorCode::UnauthorizedResolver
         );
+        require!(executor.active, ErrorCode::ExecutorInactive);
+        require!(
+            executor.bond >= ctx.accounts.protocol_config.executor_min_bond,
+            ErrorCode::ExecutorBondTooLow
+        );
 
-        market.status = MarketStatus::Resolved;
-        market.outcome = Some(outcome);
-        market.ipfs_transcript_hash = ipfs_transcript_hash;
+        apply_resolution(
+            market,
+            Resolution {
+                outcome,
+                ipfs_transcript_hash,
+                confidence_bps,
+                model_id,
+                researcher_hash,
+                judge_hash,
+                settlement_bps,
+            },
+            ctx.accounts.authority.key(),
+            Clock::get()?.unix_timestamp,
+        )?;
 
-        // Increment executor stats
+        // Increment executor stats; a resolution counts as upheld until a dispute overturns it
         let executor_mut = &mut ctx.accounts.agent_executor;
         executor_mut.markets_resolved = executor_mut.markets_resolved.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        executor_mut.resolutions_upheld = executor_mut.resolutions_upheld.checked_add(1).ok_or(ErrorCode::Overflow)?;
 
-        let timestamp = Clock::get()?.unix_timestamp;
+        msg!("Market {} resolved with outcome: {} (confidence {} bps)", market.key(), outcome, confidence_bps);
+        Ok(())
+    }
 
-        emit!(MarketResolved {
-            market: market.key(),
-            outcome,
-            ipfs_transcript_hash,
-            resolver: ctx.accounts.authority.key(),
-            timestamp,
-        });
+    /// Resolve a market with a verdict signed by its executor's key, submitted by any relayer.
+    /// The transaction must carry an Ed25519 program instruction, directly before this one,
+    /// verifying the executor's signature over `VERDICT_DOMAIN` + the serialized verdict.
+    /// A verdict names its market and only resolves an open one, so it cannot be replayed
+    /// and verdicts for different markets can land in any order. Its nonce must exceed the
+    /// market's `verdict_nonce`, which the executor can raise to revoke verdicts it signed.
+    pub fn resolve_market_signed(ctx: Context<ResolveMarketSigned>, verdict: SignedVerdict) -> Result<()> {
+        require!(verdict.outcome <= OUTCOME_INVALID, ErrorCode::InvalidOutcome);
+        require!(verdict.confidence_bps <= BPS_DENOMINATOR, ErrorCode::InvalidConfidence);
+        require!(verdict.model_id.len() <= MAX_MODEL_ID_LEN, ErrorCode::ModelIdTooLong);
+        if let Some(bps) = verdict.settlement_bps {
+            require!(bps <= BPS_DENOMINATOR, ErrorCode::InvalidSettlement);
+            require!(verdict.outcome != OUTCOME_INVALID, ErrorCode::InvalidSettlement);
+        }
 
-        // Emit NFT mint request
-        emit!(ProofNFTMintRequested {
-            market: market.key(),
-            outcome,
-            ipfs_transcript_hash,
-            timestamp,
-        });
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(market.ensemble_quorum == 0, ErrorCode::EnsembleMarket);
+        require_keys_eq!(verdict.market, market.key(), ErrorCode::InvalidSignedVerdict);
+        require!(verdict.nonce > market.verdict_nonce, ErrorCode::StaleVerdictNonce);
 
-        msg!("Market {} resolved with outcome: {}", market.key(), outcome);
-        Ok(())
-    }
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= verdict.expiry, ErrorCode::VerdictExpired);
 
-    /// Distribute Cred rewards from InsightPool to winners
-    pub fn distribute_insight_rewards(
-        ctx: Context<DistributeInsightRewards>,
-        amount: u64,
-    ) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
-        
-        let market = &ctx.accounts.market;
-        require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
-        
-        let stake = &ctx.accounts.cred_stake;
-        let outcome = market.outcome.ok_or(ErrorCode::MarketNotResolved)?;
-        
-        // Check if user won (their direction matches the outcome)
-        let user_won = (stake.direction && outcome == 1) || (!stake.direction && outcome == 0);
-        require!(user_won, ErrorCode::UserDidNotWin);
+        let executor = &mut ctx.accounts.agent_executor;
+        require!(executor.active, ErrorCode::ExecutorInactive);
+        require!(
+            executor.bond >= ctx.accounts.protocol_config.executor_min_bond,
+            ErrorCode::ExecutorBondTooLow
+        );
+        let mut message = VERDICT_DOMAIN.to_vec();
+        verdict.serialize(&mut message)?;
+        verify_ed25519_signature(&ctx.accounts.instructions, &executor.authority, &message)?;
 
-        // Update recipient's vault
-        let vault = &mut ctx.accounts.recipient_vault;
-        vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-        vault.total_earned = vault.total_earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        market.verdict_nonce = verdict.nonce;
+        apply_resolution(
+            market,
+            Resolution {
+                outcome: verdict.outcome,
+                ipfs_transcript_hash: verdict.ipfs_transcript_hash,
+                confidence_bps: verdict.confidence_bps,
+                model_id: verdict.model_id.clone(),
+                researcher_hash: verdict.researcher_hash,
+                judge_hash: verdict.judge_hash,
+                settlement_bps: verdict.settlement_bps,
+            },
+            executor.authority,
+            now,
+        )?;
 
-        // Update insight pool
-        let pool = &mut ctx.accounts.insight_pool;
-        pool.total_credits = pool.total_credits.checked_add(amount).ok_or(ErrorCode::Overflow)?;
-        pool.distributions_count = pool.distributions_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
-        pool.last_distribution = Clock::get()?.unix_timestamp;
+        executor.markets_resolved = executor.markets_resolved.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        executor.resolutions_upheld = executor.resolutions_upheld.checked_add(1).ok_or(ErrorCode::Overflow)?;
 
-        emit!(CredDistributed {
+        emit!(SignedVerdictRelayed {
             market: market.key(),
-            recipient: vault.owner,
-            amount,
-            timestamp: pool.last_distribution,
+            executor: executor.key(),
+            relayer: ctx.accounts.relayer.key(),
+            nonce: verdict.nonce,
+            timestamp: now,
         });
 
-        msg!("Distributed {} Cred to {}", amount, vault.owner);
+        msg!("Market {} resolved by signed verdict {} with outcome: {}", market.key(), verdict.nonce, verdict.outcome);
         Ok(())
     }
 
-    /// Earn Cred through various contribution methods
-    pub fn earn_cred(
-        ctx: Context<EarnCred>,
-        amount: u64,
-        method: EarnMethod,
-    ) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
-        
-        // Verify authority (only authorized callers can mint Cred)
+    /// Revoke every signed verdict for an open market with a nonce up to `nonce`.
+    /// ONLY callable by the market's executor authority.
+    pub fn revoke_signed_verdicts(ctx: Context<RevokeSignedVerdicts>, nonce: u64) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.agent_executor.authority,
+            ErrorCode::UnauthorizedResolver
+        );
+
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(nonce > market.verdict_nonce, ErrorCode::StaleVerdictNonce);
+        market.verdict_nonce = nonce;
+
+        msg!("Signed verdicts for market {} revoked up to nonce {}", market.key(), nonce);
+        Ok(())
+    }
+
+    /// Require `quorum` of the listed executors to agree before the market resolves; only
+    /// they may submit verdicts. ONLY callable by the market creator, while the market is
+    /// open and has no verdicts.
+    pub fn enable_ensemble<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EnableEnsemble<'info>>,
+        quorum: u8,
+        executors: Vec<Pubkey>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(market.verdict_count == 0, ErrorCode::InvalidEnsemble);
+        // Stakers committed under the market's single executor
+        require!(
+            market.total_yes_stake == 0 && market.total_no_stake == 0,
+            ErrorCode::EnsembleAfterStakes
+        );
+        require!(executors.len() <= MAX_ENSEMBLE_SIZE, ErrorCode::InvalidEnsemble);
+        let size = executors.len() as u8;
+        require!(quorum >= 2 && quorum <= size, ErrorCode::InvalidEnsemble);
+        for (i, executor) in executors.iter().enumerate() {
+            require!(!executors[..i].contains(executor), ErrorCode::InvalidEnsemble);
+        }
+
+        // Each executor's account is passed in order, and must be able to resolve today
+        require!(ctx.remaining_accounts.len() == executors.len(), ErrorCode::InvalidEnsemble);
+        for (key, info) in executors.iter().zip(ctx.remaining_accounts) {
+            require!(info.key == key, ErrorCode::InvalidEnsemble);
+            let executor = Account::<AgentExecutor>::try_from(info)?;
+            require!(executor.active, ErrorCode::ExecutorInactive);
+            require!(
+                executor.bond >= ctx.accounts.protocol_config.executor_min_bond,
+                ErrorCode::ExecutorBondTooLow
+            );
+        }
+
+        market.ensemble_quorum = quorum;
+        market.ensemble_size = size;
+        market.ensemble_executors = executors;
+
+        msg!("Market {} requires {} of {} executor verdicts", market.key(), quorum, size);
+        Ok(())
+    }
+
+    /// Record one executor's verdict on an ensemble market. The market resolves once
+    /// `ensemble_quorum` verdicts agree; if no outcome can still reach the quorum, the
+    /// market is escalated unresolved into the dispute flow and `settle_dispute` rules on it.
+    pub fn submit_verdict(
+        ctx: Context<SubmitVerdict>,
+        outcome: u8,
+        ipfs_transcript_hash: [u8; 32],
+        confidence_bps: u16,
+    ) -> Result<()> {
+        require!(outcome <= OUTCOME_INVALID, ErrorCode::InvalidOutcome);
+        require!(confidence_bps <= BPS_DENOMINATOR, ErrorCode::InvalidConfidence);
+
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(market.ensemble_quorum > 0, ErrorCode::NotEnsembleMarket);
+
         let executor = &ctx.accounts.agent_executor;
+        require!(
+            market.ensemble_executors.contains(&executor.key()),
+            ErrorCode::ExecutorNotInEnsemble
+        );
         require!(
             ctx.accounts.authority.key() == executor.authority,
+            ErrorCode::UnauthorizedResolver
+        );
+        require!(executor.active, ErrorCode::ExecutorInactive);
+        require!(
+            executor.bond >= ctx.accounts.protocol_config.executor_min_bond,
+            ErrorCode::ExecutorBondTooLow
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let verdict = &mut ctx.accounts.verdict;
+        verdict.market = market.key();
+        verdict.executor = executor.key();
+        verdict.outcome = outcome;
+        verdict.ipfs_transcript_hash = ipfs_transcript_hash;
+        verdict.confidence_bps = confidence_bps;
+        verdict.submitted_at = now;
+        verdict.bump = ctx.bumps.verdict;
+
+        let index = outcome as usize;
+        market.verdict_count = market.verdict_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        market.verdict_tallies[index] = market.verdict_tallies[index].checked_add(1).ok_or(ErrorCode::Overflow)?;
+        market.verdict_confidence_total[index] = market.verdict_confidence_total[index]
+            .checked_add(confidence_bps as u32)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(VerdictSubmitted {
+            market: market.key(),
+            executor: executor.key(),
+            outcome,
+            ipfs_transcript_hash,
+            confidence_bps,
+            timestamp: now,
+        });
+
+        match ensemble_decision(market) {
+            EnsembleDecision::Pending => {
+                msg!("Verdict {} of {} recorded for market {}", market.verdict_count, market.ensemble_size, market.key());
+            }
+            EnsembleDecision::Quorum(agreed) => {
+                // The verdict completing the quorum supplies the transcript; confidence is the agreeing mean
+                let index = agreed as usize;
+                let confidence_bps = (market.verdict_confidence_total[index] / market.verdict_tallies[index] as u32) as u16;
+                apply_resolution(
+                    market,
+                    Resolution {
+                        outcome: agreed,
+                        ipfs_transcript_hash,
+                        confidence_bps,
+                        model_id: ENSEMBLE_MODEL_ID.to_string(),
+                        researcher_hash: [0u8; 32],
+                        judge_hash: [0u8; 32],
+                        settlement_bps: None,
+                    },
+                    ctx.accounts.authority.key(),
+                    now,
+                )?;
+                msg!("Ensemble quorum resolved market {} with outcome {}", market.key(), agreed);
+            }
+            EnsembleDecision::Split => {
+                // No outcome is applied; the dispute authority supplies it
+                market.status = MarketStatus::Disputed;
+                market.dispute_status = DisputeStatus::Pending;
+
+                emit!(EnsembleSplit {
+                    market: market.key(),
+                    verdict_tallies: market.verdict_tallies,
+                    timestamp: now,
+                });
+                msg!("Ensemble split on market {}; escalated to dispute", market.key());
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the next InsightPool emission epoch once the current one has elapsed (permissionless).
+    /// The new epoch's budget follows the decay schedule; unspent budget does not carry over.
+    pub fn crank_emission(ctx: Context<CrankEmission>) -> Result<()> {
+        let config = &ctx.accounts.protocol_config;
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut ctx.accounts.insight_pool;
+        require!(
+            pool.advance_epoch(now, config.emission_epoch_duration)?,
+            ErrorCode::EmissionNotDue
+        );
+
+        let budget = decayed_emission(config.emission_per_epoch, config.emission_decay_bps, pool.epoch - 1)?;
+        pool.epoch_budget = budget;
+        pool.remaining_budget = budget;
+        pool.total_emitted = pool.total_emitted.checked_add(budget).ok_or(ErrorCode::Overflow)?;
+
+        emit!(EmissionCranked {
+            epoch: pool.epoch,
+            budget,
+            epoch_start: pool.epoch_start,
+            timestamp: now,
+        });
+
+        msg!("InsightPool epoch {} opened with a budget of {} Cred", pool.epoch, budget);
+        Ok(())
+    }
+
+    /// Distribute Cred rewards from InsightPool to winners, within the current epoch budget
+    pub fn distribute_insight_rewards(
+        ctx: Context<DistributeInsightRewards>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.agent_executor.authority,
             ErrorCode::UnauthorizedMinter
         );
+        
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
+        resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, Clock::get()?.unix_timestamp)?;
+        
+        let stake = &mut ctx.accounts.cred_stake;
+        let outcome = market.outcome.ok_or(ErrorCode::MarketNotResolved)?;
+        require!(outcome != OUTCOME_INVALID, ErrorCode::MarketInvalid);
+        
+        // Check if user won (their direction matches the outcome)
+        let user_won = (stake.direction && outcome == OUTCOME_YES) || (!stake.direction && outcome == OUTCOME_NO);
+        require!(user_won, ErrorCode::UserDidNotWin);
 
-        let vault = &mut ctx.accounts.reputation_vault;
+        // Each winning stake is rewarded once
+        require!(!stake.insight_distributed, ErrorCode::InsightAlreadyDistributed);
+        stake.insight_distributed = true;
+
+        // Low-confidence verdicts pay out a reduced reward
+        let amount = scale_by_confidence(amount, market.confidence_bps)?;
+        let pool = &mut ctx.accounts.insight_pool;
+        pool.remaining_budget = pool
+            .remaining_budget
+            .checked_sub(amount)
+            .ok_or(ErrorCode::EmissionBudgetExhausted)?;
+
+        // Update recipient's vault
+        let vault = &mut ctx.accounts.recipient_vault;
         vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
         vault.total_earned = vault.total_earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
 
-        emit!(CredEarned {
-            user: vault.owner,
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, amount)?;
+        }
+        ctx.accounts.cred_ledger.record_mint(amount)?;
+        market.insight_pool_amount = market.insight_pool_amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        // Update insight pool
+        pool.total_credits = pool.total_credits.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        pool.distributions_count = pool.distributions_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        pool.last_distribution = Clock::get()?.unix_timestamp;
+
+        emit!(CredDistributed {
+            market: market.key(),
+            recipient: vault.owner,
             amount,
-            method,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp: pool.last_distribution,
         });
 
-        msg!("Earned {} Cred via {:?} for {}", amount, method, vault.owner);
+        msg!("Distributed {} Cred to {}", amount, vault.owner);
         Ok(())
     }
 
-    /// Dispute a market resolution (sets status to Disputed)
-    pub fn dispute_market(ctx: Context<DisputeMarket>) -> Result<()> {
+    /// Claim a Cred stake's pari-mutuel payout once the market is resolved (permissionless).
+    /// YES stakers share `settlement_bps` of the pool, NO stakers share the remainder.
+    pub fn claim_cred_stake(ctx: Context<ClaimCredStake>) -> Result<()> {
         let market = &mut ctx.accounts.market;
         require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
-        
-        market.status = MarketStatus::Disputed;
+        require!(market.outcome != Some(OUTCOME_INVALID), ErrorCode::MarketInvalid);
+        // Nothing is paid until a dispute could no longer re-settle the market
+        resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, Clock::get()?.unix_timestamp)?;
 
-        emit!(MarketDisputed {
+        let stake = &mut ctx.accounts.cred_stake;
+        require!(!stake.claimed, ErrorCode::StakeAlreadyClaimed);
+        stake.claimed = true;
+
+        let payout = settlement_payout(market, stake.amount, stake.direction)?;
+        market.settled_stake = market.settled_stake.checked_add(stake.amount).ok_or(ErrorCode::Overflow)?;
+        market.total_paid_out = market.total_paid_out.checked_add(payout).ok_or(ErrorCode::Overflow)?;
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        if payout > 0 {
+            vault.cred_balance = vault.cred_balance.checked_add(payout).ok_or(ErrorCode::Overflow)?;
+            vault.total_earned = vault.total_earned.checked_add(payout).ok_or(ErrorCode::Overflow)?;
+
+            // Token mode: pay out of the market escrow
+            if let Some(token) = CredToken::load(
+                &ctx.accounts.protocol_config,
+                ctx.accounts.cred_mint.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            )? {
+                let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+                let escrow = token.escrow_account(ctx.accounts.market_escrow.as_deref(), &market.key())?;
+                token.transfer(
+                    escrow,
+                    cred_account,
+                    market.to_account_info(),
+                    &[&[MARKET_SEED, market.market_id.as_bytes(), &[market.bump]]],
+                    payout,
+                )?;
+            }
+            ctx.accounts.cred_ledger.record_payout(payout)?;
+        }
+
+        // Winners also share the market's own pool until it is swept. That Cred was
+        // burned when boosted, so it is minted back.
+        let pool_share = if market.market_pool_swept {
+            0
+        } else {
+            winner_share(market, market.market_pool, stake.amount, stake.direction)?
+        };
+        if pool_share > 0 {
+            market.market_pool_paid_out = market.market_pool_paid_out.checked_add(pool_share).ok_or(ErrorCode::Overflow)?;
+            vault.cred_balance = vault.cred_balance.checked_add(pool_share).ok_or(ErrorCode::Overflow)?;
+            vault.total_earned = vault.total_earned.checked_add(pool_share).ok_or(ErrorCode::Overflow)?;
+
+            if let Some(token) = CredToken::load(
+                &ctx.accounts.protocol_config,
+                ctx.accounts.cred_mint.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            )? {
+                let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+                token.mint_to(cred_account, pool_share)?;
+            }
+            ctx.accounts.cred_ledger.record_mint(pool_share)?;
+        }
+
+        // Score the stake as a 0%/100% forecast against the settlement
+        let forecast_bps = if stake.direction { BPS_DENOMINATOR } else { 0 };
+        record_settlement(vault, forecast_bps, market.settlement_bps, stake.amount)?;
+
+        let token = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        unlock_grant_tranche(
+            &ctx.accounts.protocol_config,
+            &mut ctx.accounts.cred_ledger,
+            vault,
+            stake.amount,
+            token.as_ref(),
+            ctx.accounts.cred_account.as_deref(),
+        )?;
+
+        // Once the referee has settled enough stake, their referrer's bonus unlocks
+        if let Some(referrer) = ctx.accounts.referrer_vault.as_mut() {
+            if referral_bonus_due(&ctx.accounts.protocol_config, vault) {
+                let token = CredToken::load(
+                    &ctx.accounts.protocol_config,
+                    ctx.accounts.cred_mint.as_deref(),
+                    ctx.accounts.token_program.as_ref(),
+                )?;
+                pay_referral_bonus(
+                    &ctx.accounts.protocol_config,
+                    &mut ctx.accounts.cred_ledger,
+                    vault,
+                    referrer,
+                    token.as_ref(),
+                    ctx.accounts.referrer_cred_account.as_deref(),
+                )?;
+            }
+        }
+        record_season_settlement(
+            ctx.accounts.season.as_ref(),
+            ctx.accounts.season_stats.as_mut(),
+            &vault.owner,
+            forecast_bps,
+            market.settlement_bps,
+            stake.amount,
+            payout,
+        )?;
+
+        emit!(CredStakeClaimed {
             market: market.key(),
-            disputer: ctx.accounts.disputer.key(),
+            user: stake.user,
+            amount: stake.amount,
+            direction: stake.direction,
+            payout,
+            pool_share,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Market {} disputed", market.key());
+        msg!("Claimed {} Cred for {} on market {}", payout, stake.user, market.key());
         Ok(())
     }
 
-    /// Stake Cred on the Oracle's accuracy (betting AI will resolve correctly)
-    pub fn stake_on_oracle(
-        ctx: Context<StakeOnOracle>,
-        amount: u64,
-    ) -> Result<()> {
+    /// Add Cred to an open market's own pool, shared by its winners on top of their
+    /// pari-mutuel payout. Creators fund it by boosting in the creation transaction.
+    /// Each booster's total is recorded so it can be refunded if the market is voided.
+    pub fn boost_market(ctx: Context<BoostMarket>, amount: u64) -> Result<()> {
         require!(amount > 0, ErrorCode::InvalidAmount);
-        
-        let vault = &mut ctx.accounts.reputation_vault;
-        let market = &ctx.accounts.market;
-        
+        let market = &mut ctx.accounts.market;
         require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
-        require!(vault.cred_balance >= amount, ErrorCode::InsufficientCred);
 
-        // Deduct from vault
+        let vault = &mut ctx.accounts.reputation_vault;
+        require!(vault.cred_balance >= amount, ErrorCode::InsufficientCred);
         vault.cred_balance = vault.cred_balance.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
-        vault.total_staked = vault.total_staked.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        vault.spend_faucet_first(amount);
+        market.market_pool = market.market_pool.checked_add(amount).ok_or(ErrorCode::Overflow)?;
 
-        // Create oracle stake record
-        let oracle_stake = &mut ctx.accounts.oracle_stake;
-        oracle_stake.user = ctx.accounts.user.key();
-        oracle_stake.market = market.key();
-        oracle_stake.amount = amount;
-        oracle_stake.timestamp = Clock::get()?.unix_timestamp;
-        oracle_stake.claimed = false;
-        oracle_stake.bump = ctx.bumps.oracle_stake;
+        let boost = &mut ctx.accounts.market_boost;
+        boost.market = market.key();
+        boost.booster = vault.owner;
+        boost.amount = boost.amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        boost.bump = ctx.bumps.market_boost;
 
-        emit!(OracleStaked {
+        // Token mode: boosts are burned, and winners are minted their share
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.burn(cred_account, ctx.accounts.booster.to_account_info(), &[], amount)?;
+        }
+        ctx.accounts.cred_ledger.record_burn(amount)?;
+
+        emit!(MarketPoolBoosted {
             market: market.key(),
-            user: oracle_stake.user,
+            booster: vault.owner,
             amount,
-            timestamp: oracle_stake.timestamp,
+            total: market.market_pool,
+            by_creator: vault.owner == market.creator,
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
-        msg!("Staked {} Cred on Oracle for market {}", amount, market.key());
+        msg!("Boosted market {} pool by {} Cred", market.key(), amount);
         Ok(())
     }
 
-    /// Resolve Oracle stakes after market is resolved (winners get 2x)
-    pub fn resolve_oracle_stake(
-        ctx: Context<ResolveOracleStake>,
-        market_was_disputed: bool,
-    ) -> Result<()> {
-        let oracle_stake = &mut ctx.accounts.oracle_stake;
-        let market = &ctx.accounts.market;
-        let vault = &mut ctx.accounts.reputation_vault;
-        
-        // Market must be resolved or disputed
+    /// Return a market pool's unclaimed Cred to the InsightPool's current budget once
+    /// `market_pool_claim_window` has passed since resolution (permissionless). The pool of
+    /// a voided or Invalid market belongs to its boosters, who reclaim it with `refund_market_boost`.
+    pub fn sweep_market_pool(ctx: Context<SweepMarketPool>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
+        require!(market.outcome != Some(OUTCOME_INVALID), ErrorCode::MarketInvalid);
+        require!(!market.market_pool_swept, ErrorCode::MarketPoolSwept);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= market.resolved_at.saturating_add(ctx.accounts.protocol_config.market_pool_claim_window),
+            ErrorCode::ClaimWindowOpen
+        );
+
+        let remainder = market
+            .market_pool
+            .checked_sub(market.market_pool_paid_out)
+            .and_then(|v| v.checked_sub(market.market_pool_refunded))
+            .ok_or(ErrorCode::Overflow)?;
+        market.market_pool_swept = true;
+        market.market_pool_swept_amount = remainder;
+
+        let pool = &mut ctx.accounts.insight_pool;
+        pool.remaining_budget = pool.remaining_budget.checked_add(remainder).ok_or(ErrorCode::Overflow)?;
+        pool.market_pools_swept = pool.market_pools_swept.checked_add(remainder).ok_or(ErrorCode::Overflow)?;
+
+        emit!(MarketPoolSwept {
+            market: market.key(),
+            amount: remainder,
+            timestamp: now,
+        });
+
+        msg!("Swept {} unclaimed Cred from market {} to the InsightPool", remainder, market.key());
+        Ok(())
+    }
+
+    /// Void a market its executor never resolved (permissionless). Once `market_max_age`
+    /// has passed since creation, the market is marked Invalid so every Cred and Oracle
+    /// stake can be refunded, and the caller is minted `expiry_crank_reward` Cred.
+    pub fn expire_market(ctx: Context<ExpireMarket>) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= market.created_at.saturating_add(config.market_max_age),
+            ErrorCode::MarketNotExpired
+        );
+
+        market.status = MarketStatus::Voided;
+        market.outcome = Some(OUTCOME_INVALID);
+        market.resolved_at = now;
+
+        let reward = config.expiry_crank_reward;
+        if reward > 0 {
+            // Crank rewards share the daily mint ceiling with earn_cred
+            config.record_daily_mint(reward, now.div_euclid(SECONDS_PER_DAY))?;
+            let vault = &mut ctx.accounts.caller_vault;
+            vault.cred_balance = vault.cred_balance.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+            vault.total_earned = vault.total_earned.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+
+            if let Some(token) = CredToken::load(
+                config,
+                ctx.accounts.cred_mint.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            )? {
+                let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+                token.mint_to(cred_account, reward)?;
+            }
+            ctx.accounts.cred_ledger.record_mint(reward)?;
+        }
+
+        emit!(MarketExpired {
+            market: market.key(),
+            caller: ctx.accounts.caller.key(),
+            crank_reward: reward,
+            timestamp: now,
+        });
+
+        msg!("Market {} expired unresolved and was voided", market.key());
+        Ok(())
+    }
+
+    /// Refund a Cred stake at par on a market resolved as Invalid or voided (permissionless)
+    pub fn refund_cred_stake(ctx: Context<RefundCredStake>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
         require!(
-            market.status == MarketStatus::Resolved || market.status == MarketStatus::Disputed,
+            matches!(market.status, MarketStatus::Resolved | MarketStatus::Voided),
             ErrorCode::MarketNotResolved
         );
-        require!(!oracle_stake.claimed, ErrorCode::OracleStakeAlreadyClaimed);
-        
-        // Verify the signer is the agent executor authority
-        let executor = &ctx.accounts.agent_executor;
+        require!(market.outcome == Some(OUTCOME_INVALID), ErrorCode::MarketNotInvalid);
+        // An Invalid verdict can still be overturned; a voided market is final
+        if market.status == MarketStatus::Resolved {
+            resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, Clock::get()?.unix_timestamp)?;
+        }
+
+        let stake = &mut ctx.accounts.cred_stake;
+        require!(!stake.claimed, ErrorCode::StakeAlreadyClaimed);
+        stake.claimed = true;
+        market.settled_stake = market.settled_stake.checked_add(stake.amount).ok_or(ErrorCode::Overflow)?;
+        market.total_refunded = market.total_refunded.checked_add(stake.amount).ok_or(ErrorCode::Overflow)?;
+
+        // A refunded stake no longer counts as Cred put at risk
+        let vault = &mut ctx.accounts.reputation_vault;
+        vault.cred_balance = vault.cred_balance.checked_add(stake.amount).ok_or(ErrorCode::Overflow)?;
+        vault.total_staked = vault.total_staked.saturating_sub(stake.amount);
+
+        // Token mode: return the stake from the market escrow
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            let escrow = token.escrow_account(ctx.accounts.market_escrow.as_deref(), &market.key())?;
+            token.transfer(
+                escrow,
+                cred_account,
+                market.to_account_info(),
+                &[&[MARKET_SEED, market.market_id.as_bytes(), &[market.bump]]],
+                stake.amount,
+            )?;
+        }
+        ctx.accounts.cred_ledger.record_refund(stake.amount)?;
+
+        emit!(CredStakeRefunded {
+            market: market.key(),
+            user: stake.user,
+            amount: stake.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Refunded {} Cred to {} for invalid market {}", stake.amount, stake.user, market.key());
+        Ok(())
+    }
+
+    /// Check that a market's stakes, payouts, refunds and escrow balance against each
+    /// other and the global Cred ledger (permissionless). Fails with InvariantViolation.
+    pub fn audit_market(ctx: Context<AuditMarket>) -> Result<()> {
+        let market = &ctx.accounts.market;
+
+        // Token mode: balance the flows against what the escrow actually holds
+        let escrow_balance = match CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            Some(token) => Some(token.escrow_account(ctx.accounts.market_escrow.as_deref(), &market.key())?.amount),
+            None => None,
+        };
+        let escrowed = audit_market_flows(market, &ctx.accounts.cred_ledger, escrow_balance)?;
+
+        emit!(MarketAudited {
+            market: market.key(),
+            total_staked: market.total_yes_stake.saturating_add(market.total_no_stake),
+            total_paid_out: market.total_paid_out,
+            total_refunded: market.total_refunded,
+            insight_pool_amount: market.insight_pool_amount,
+            escrowed,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Market {} audited: {} Cred escrowed", market.key(), escrowed);
+        Ok(())
+    }
+
+    /// Pay a referrer's bonus once their referee has settled enough stake (permissionless)
+    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
         require!(
-            ctx.accounts.authority.key() == executor.authority,
-            ErrorCode::UnauthorizedResolver
+            referral_bonus_due(&ctx.accounts.protocol_config, &ctx.accounts.referee_vault),
+            ErrorCode::ReferralBonusNotDue
+        );
+
+        let token = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        pay_referral_bonus(
+            &ctx.accounts.protocol_config,
+            &mut ctx.accounts.cred_ledger,
+            &mut ctx.accounts.referee_vault,
+            &mut ctx.accounts.referrer_vault,
+            token.as_ref(),
+            ctx.accounts.referrer_cred_account.as_deref(),
+        )
+    }
+
+    /// Create the top-N Leaderboard for a ranking metric
+    pub fn initialize_leaderboard(
+        ctx: Context<InitializeLeaderboard>,
+        metric: LeaderboardMetric,
+    ) -> Result<()> {
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.metric = metric;
+        leaderboard.entries = Vec::new();
+        leaderboard.last_updated = Clock::get()?.unix_timestamp;
+        leaderboard.bump = ctx.bumps.leaderboard;
+
+        msg!("Leaderboard initialized for {:?}", metric);
+        Ok(())
+    }
+
+    /// Submit a vault to a Leaderboard (permissionless). The vault's current score
+    /// replaces any previous entry and the list is re-sorted and truncated to N.
+    pub fn submit_to_leaderboard(ctx: Context<SubmitToLeaderboard>) -> Result<()> {
+        let vault = &ctx.accounts.reputation_vault;
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        let score = leaderboard_score(vault, leaderboard.metric);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        leaderboard.submit(vault.owner, score, timestamp);
+        let rank = leaderboard.rank_of(&vault.owner);
+
+        emit!(LeaderboardUpdated {
+            leaderboard: leaderboard.key(),
+            metric: leaderboard.metric,
+            owner: vault.owner,
+            score,
+            rank: rank.map(|r| r as u8),
+            timestamp,
+        });
+
+        msg!("Leaderboard {:?}: {} scored {} (rank {:?})", leaderboard.metric, vault.owner, score, rank);
+        Ok(())
+    }
+
+    /// Open the first Season - ONLY callable by the ProtocolConfig authority
+    pub fn initialize_season(ctx: Context<InitializeSeason>, duration: i64) -> Result<()> {
+        require!(duration > 0, ErrorCode::InvalidSeasonDuration);
+
+        let config = &mut ctx.accounts.protocol_config;
+        require!(
+            ctx.accounts.authority.key() == config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+        require!(config.current_season == 0, ErrorCode::SeasonAlreadyStarted);
+
+        let now = Clock::get()?.unix_timestamp;
+        let season = &mut ctx.accounts.season;
+        open_season(season, 1, now, duration, ctx.bumps.season)?;
+        config.current_season = 1;
+
+        msg!("Season 1 started, ends at {}", season.end_ts);
+        Ok(())
+    }
+
+    /// Create a user's stats account for the active Season
+    pub fn join_season(ctx: Context<JoinSeason>) -> Result<()> {
+        let season = &ctx.accounts.season;
+        require!(season.status == SeasonStatus::Active, ErrorCode::SeasonNotActive);
+
+        let stats = &mut ctx.accounts.season_stats;
+        stats.season = season.key();
+        stats.owner = ctx.accounts.reputation_vault.owner;
+        stats.cred_earned = 0;
+        stats.cred_staked = 0;
+        stats.wins = 0;
+        stats.losses = 0;
+        stats.settled_count = 0;
+        stats.brier_score_total = 0;
+        stats.bump = ctx.bumps.season_stats;
+
+        msg!("{} joined season {}", stats.owner, season.number);
+        Ok(())
+    }
+
+    /// Submit a user's season stats to the Season leaderboard (permissionless)
+    pub fn submit_season_stats(ctx: Context<SubmitSeasonStats>) -> Result<()> {
+        let season = &mut ctx.accounts.season;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            season.status == SeasonStatus::Active && now < season.end_ts,
+            ErrorCode::SeasonNotActive
         );
 
-        oracle_stake.claimed = true;
-        
-        // Oracle stakers win if market was NOT disputed
-        // If disputed, they lose their stake
-        if !market_was_disputed && market.status == MarketStatus::Resolved {
-            // Winner - return 2x stake
-            let reward = oracle_stake.amount.checked_mul(2).ok_or(ErrorCode::Overflow)?;
-            vault.cred_balance = vault.cred_balance.checked_add(reward).ok_or(ErrorCode::Overflow)?;
-            vault.total_earned = vault.total_earned.checked_add(reward).ok_or(ErrorCode::Overflow)?;
-            
-            emit!(OracleStakeResolved {
-                market: market.key(),
-                user: oracle_stake.user,
-                amount: oracle_stake.amount,
-                reward,
-                won: true,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
-            
-            msg!("Oracle stake resolved: {} won {} Cred", oracle_stake.user, reward);
-        } else {
-            // Loser - stake already deducted, just emit event
-            emit!(OracleStakeResolved {
-                market: market.key(),
-                user: oracle_stake.user,
-                amount: oracle_stake.amount,
-                reward: 0,
-                won: false,
-                timestamp: Clock::get()?.unix_timestamp,
-            });
-            
-            msg!("Oracle stake resolved: {} lost {} Cred (market was disputed)", oracle_stake.user, oracle_stake.amount);
-        }
-        
-        Ok(())
-    }
+        let stats = &ctx.accounts.season_stats;
+        let score = stats.cred_earned.saturating_sub(stats.cred_staked);
+        insert_ranked(&mut season.leaderboard, stats.owner, score);
+
+        emit!(LeaderboardUpdated {
+            leaderboard: season.key(),
+            metric: LeaderboardMetric::NetCredEarned,
+            owner: stats.owner,
+            score,
+            rank: rank_in(&season.leaderboard, &stats.owner).map(|r| r as u8),
+            timestamp: now,
+        });
+
+        msg!("Season {}: {} scored {}", season.number, stats.owner, score);
+        Ok(())
+    }
+
+    /// End the current Season once its end time has passed and open the next one (permissionless).
+    /// The ended Season keeps its leaderboard as the archived standings.
+    pub fn end_season(ctx: Context<EndSeason>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let current = &mut ctx.accounts.current_season;
+        require!(current.status == SeasonStatus::Active, ErrorCode::SeasonNotActive);
+        require!(now >= current.end_ts, ErrorCode::SeasonNotOver);
+
+        current.status = SeasonStatus::Ended;
+        let winners: Vec<LeaderboardEntry> = current
+            .leaderboard
+            .iter()
+            .take(SEASON_WINNERS_COUNT)
+            .copied()
+            .collect();
+
+        emit!(SeasonEnded {
+            season: current.key(),
+            number: current.number,
+            winners,
+            timestamp: now,
+        });
+
+        let next_number = current.number.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        let duration = current.duration;
+        let next = &mut ctx.accounts.next_season;
+        open_season(next, next_number, now, duration, ctx.bumps.next_season)?;
+        ctx.accounts.protocol_config.current_season = next_number;
+
+        msg!("Season {} ended, season {} started", next_number - 1, next_number);
+        Ok(())
+    }
+
+    /// Earn Cred through various contribution methods
+    pub fn earn_cred(
+        ctx: Context<EarnCred>,
+        amount: u64,
+        method: EarnMethod,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        // Verify authority (only authorized callers can mint Cred)
+        let executor = &ctx.accounts.agent_executor;
+        require!(
+            ctx.accounts.authority.key() == executor.authority,
+            ErrorCode::UnauthorizedMinter
+        );
+
+        // Enforce the per-vault method cap and the global daily mint ceiling
+        let today = Clock::get()?.unix_timestamp.div_euclid(SECONDS_PER_DAY);
+        let config = &mut ctx.accounts.protocol_config;
+        let vault = &mut ctx.accounts.reputation_vault;
+        if vault.earn_day != today {
+            vault.earn_day = today;
+            vault.earned_today_by_method = [0; EARN_METHOD_COUNT];
+        }
+        let earned_today = vault.earned_today_by_method[method as usize]
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            earned_today <= config.earn_method_daily_caps[method as usize],
+            ErrorCode::EarnMethodCapExceeded
+        );
+        vault.earned_today_by_method[method as usize] = earned_today;
+
+        config.record_daily_mint(amount, today)?;
+
+        vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        vault.total_earned = vault.total_earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, amount)?;
+        }
+
+        vault.record_earned(method, amount)?;
+        ctx.accounts.cred_ledger.record_mint(amount)?;
+
+        emit!(CredEarned {
+            user: vault.owner,
+            amount,
+            method,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Earned {} Cred via {:?} for {}", amount, method, vault.owner);
+        Ok(())
+    }
+
+    /// Send Cred from the signer's vault to another vault, bounded by the daily transfer limit
+    pub fn transfer_cred(
+        ctx: Context<TransferCred>,
+        amount: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        move_cred(
+            &ctx.accounts.protocol_config,
+            &mut ctx.accounts.sender_vault,
+            &mut ctx.accounts.recipient_vault,
+            amount,
+            memo.as_deref(),
+            now,
+        )?;
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let from = token.vault_account(ctx.accounts.sender_cred_account.as_deref(), &ctx.accounts.sender_vault)?;
+            let to = token.vault_account(ctx.accounts.recipient_cred_account.as_deref(), &ctx.accounts.recipient_vault)?;
+            token.transfer(from, to, ctx.accounts.sender.to_account_info(), &[], amount)?;
+        }
+
+        emit!(CredTransferred {
+            from: ctx.accounts.sender_vault.owner,
+            to: ctx.accounts.recipient_vault.owner,
+            amount,
+            memo,
+            timestamp: now,
+        });
+
+        msg!("Transferred {} Cred to {}", amount, ctx.accounts.recipient_vault.owner);
+        Ok(())
+    }
+
+    /// Tip the submitter of a market's evidence; counts toward the daily transfer limit
+    pub fn tip_evidence(
+        ctx: Context<TipEvidence>,
+        evidence_index: u8,
+        amount: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        move_cred(
+            &ctx.accounts.protocol_config,
+            &mut ctx.accounts.sender_vault,
+            &mut ctx.accounts.submitter_vault,
+            amount,
+            memo.as_deref(),
+            now,
+        )?;
+
+        let evidence = &mut ctx.accounts.evidence;
+        evidence.tips_received = evidence.tips_received.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let from = token.vault_account(ctx.accounts.sender_cred_account.as_deref(), &ctx.accounts.sender_vault)?;
+            let to = token.vault_account(ctx.accounts.recipient_cred_account.as_deref(), &ctx.accounts.submitter_vault)?;
+            token.transfer(from, to, ctx.accounts.sender.to_account_info(), &[], amount)?;
+        }
+
+        emit!(EvidenceTipped {
+            market: evidence.market,
+            evidence_index,
+            tipper: ctx.accounts.sender_vault.owner,
+            submitter: evidence.submitter,
+            amount,
+            memo,
+            timestamp: now,
+        });
+
+        msg!("Tipped {} Cred to evidence #{} on market {}", amount, evidence_index, evidence.market);
+        Ok(())
+    }
+
+    /// Dispute a market resolution within the dispute window (sets status to Disputed).
+    /// A market can only be disputed once.
+    pub fn dispute_market(ctx: Context<DisputeMarket>) -> Result<()> {
+        let config = &ctx.accounts.protocol_config;
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Resolved, ErrorCode::MarketNotResolved);
+        require!(market.dispute_status == DisputeStatus::None, ErrorCode::AlreadyDisputed);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now < market.resolved_at.saturating_add(config.dispute_window),
+            ErrorCode::DisputeWindowClosed
+        );
+        require_attestation(
+            config.require_attestation_for_disputes,
+            ctx.accounts.attestation.as_ref(),
+        )?;
+        
+        market.status = MarketStatus::Disputed;
+        market.dispute_status = DisputeStatus::Pending;
+        market.disputer = ctx.accounts.disputer.key();
+
+        emit!(MarketDisputed {
+            market: market.key(),
+            disputer: ctx.accounts.disputer.key(),
+            timestamp: now,
+        });
+
+        msg!("Market {} disputed", market.key());
+        Ok(())
+    }
+
+    /// Rule on a pending dispute - ONLY callable by the config authority.
+    /// Either way the market returns to Resolved and is final, and oracle stakes follow
+    /// the ruling. Overturning re-settles Cred stakes on `corrected_outcome` and slashes
+    /// the executor's bond: the disputer is paid their share and the rest is set aside
+    /// for the stakers the original verdict ruled against. A split ensemble has no verdict
+    /// to uphold, so it is always settled on `corrected_outcome` (Invalid voids and refunds).
+    pub fn settle_dispute(ctx: Context<SettleDispute>, upheld: bool, corrected_outcome: Option<u8>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol_config.authority,
+            ErrorCode::UnauthorizedConfigAuthority
+        );
+
+        let market = &mut ctx.accounts.market;
+        require!(
+            market.status == MarketStatus::Disputed && market.dispute_status == DisputeStatus::Pending,
+            ErrorCode::NoPendingDispute
+        );
+        market.status = MarketStatus::Resolved;
+        market.dispute_status = if upheld { DisputeStatus::Upheld } else { DisputeStatus::Overturned };
+
+        if upheld {
+            require!(
+                corrected_outcome.is_none() && market.outcome.is_some(),
+                ErrorCode::InvalidCorrectedOutcome
+            );
+        } else {
+            // Claims wait for finality, so no stake has settled on the overturned verdict
+            let corrected = corrected_outcome.ok_or(ErrorCode::InvalidCorrectedOutcome)?;
+            require!(
+                corrected <= OUTCOME_INVALID && Some(corrected) != market.outcome,
+                ErrorCode::InvalidCorrectedOutcome
+            );
+            if market.outcome.is_none() {
+                // Split ensemble: the ruling is the market's first resolution
+                market.resolved_at = Clock::get()?.unix_timestamp;
+            }
+            market.overturned_outcome = market.outcome;
+            market.outcome = Some(corrected);
+            market.settlement_bps = if corrected == OUTCOME_YES { BPS_DENOMINATOR } else { 0 };
+        }
+
+        // Ensemble markets are not resolved by a single executor, so nobody's record or bond is at stake
+        if !upheld && market.ensemble_quorum == 0 {
+            let config = &ctx.accounts.protocol_config;
+            let executor = &mut ctx.accounts.agent_executor;
+            executor.resolutions_upheld = executor.resolutions_upheld.saturating_sub(1);
+            executor.resolutions_overturned = executor.resolutions_overturned.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+            let slashed = bps_of(executor.bond, config.executor_slash_bps)?;
+            executor.bond = executor.bond.checked_sub(slashed).ok_or(ErrorCode::Overflow)?;
+            executor.total_slashed = executor.total_slashed.checked_add(slashed).ok_or(ErrorCode::Overflow)?;
+
+            // Without affected stakers the disputer takes the whole slash
+            let affected_stake = slash_affected_stake(market);
+            let disputer_share = if affected_stake == 0 {
+                slashed
+            } else {
+                bps_of(slashed, config.disputer_slash_share_bps)?
+            };
+            let staker_share = slashed - disputer_share;
+            market.slash_compensation = staker_share;
+
+            let disputer_vault = ctx.accounts.disputer_vault.as_mut().ok_or(ErrorCode::DisputerVaultRequired)?;
+            require_keys_eq!(disputer_vault.owner, market.disputer, ErrorCode::InvalidDisputerVault);
+            if disputer_share > 0 {
+                disputer_vault.cred_balance = disputer_vault.cred_balance.checked_add(disputer_share).ok_or(ErrorCode::Overflow)?;
+                disputer_vault.total_earned = disputer_vault.total_earned.checked_add(disputer_share).ok_or(ErrorCode::Overflow)?;
+
+                // Bonds are burned when posted, so slashed Cred is minted to its recipients
+                if let Some(token) = CredToken::load(
+                    config,
+                    ctx.accounts.cred_mint.as_deref(),
+                    ctx.accounts.token_program.as_ref(),
+                )? {
+                    let cred_account = token.vault_account(ctx.accounts.disputer_cred_account.as_deref(), disputer_vault)?;
+                    token.mint_to(cred_account, disputer_share)?;
+                }
+                ctx.accounts.cred_ledger.record_mint(disputer_share)?;
+            }
+
+            emit!(ExecutorSlashed {
+                executor: executor.key(),
+                market: market.key(),
+                amount: slashed,
+                disputer_share,
+                staker_share,
+                remaining_bond: executor.bond,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
+        emit!(DisputeSettled {
+            market: market.key(),
+            upheld,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Dispute on market {} settled: {}", market.key(), if upheld { "upheld" } else { "overturned" });
+        Ok(())
+    }
+
+    /// Add Cred from the depositor's vault to the executor's slashable bond. The bond is
+    /// burned in token mode, like oracle stakes, and cannot be withdrawn.
+    pub fn post_executor_bond(ctx: Context<PostExecutorBond>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let vault = &mut ctx.accounts.depositor_vault;
+        vault.cred_balance = vault.cred_balance.checked_sub(amount).ok_or(ErrorCode::InsufficientCred)?;
+        vault.spend_faucet_first(amount);
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.burn(cred_account, ctx.accounts.depositor.to_account_info(), &[], amount)?;
+        }
+        ctx.accounts.cred_ledger.record_burn(amount)?;
+
+        let executor = &mut ctx.accounts.agent_executor;
+        executor.bond = executor.bond.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        emit!(ExecutorBondPosted {
+            executor: executor.key(),
+            depositor: vault.owner,
+            amount,
+            bond: executor.bond,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Posted {} Cred to the executor bond, now {}", amount, executor.bond);
+        Ok(())
+    }
+
+    /// Pay a staker their share of the executor slash on an overturned market
+    /// (permissionless). Only stakers on the side the original verdict ruled against
+    /// are compensated, pro-rata to their stake.
+    pub fn claim_slash_compensation(ctx: Context<ClaimSlashCompensation>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.dispute_status == DisputeStatus::Overturned, ErrorCode::NotAffectedBySlash);
+
+        let stake = &mut ctx.accounts.cred_stake;
+        require!(!stake.compensated, ErrorCode::CompensationAlreadyClaimed);
+        // Split ensembles were never resolved, so nobody was ruled against
+        let overturned = market.overturned_outcome.ok_or(ErrorCode::NotAffectedBySlash)?;
+        require!(
+            overturned != OUTCOME_INVALID && stake.direction != (overturned == OUTCOME_YES),
+            ErrorCode::NotAffectedBySlash
+        );
+        stake.compensated = true;
+
+        let amount = (market.slash_compensation as u128)
+            .checked_mul(stake.amount as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / slash_affected_stake(market) as u128;
+        let amount = u64::try_from(amount).map_err(|_| ErrorCode::Overflow)?;
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        if amount > 0 {
+            vault.cred_balance = vault.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+            vault.total_earned = vault.total_earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+            if let Some(token) = CredToken::load(
+                &ctx.accounts.protocol_config,
+                ctx.accounts.cred_mint.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            )? {
+                let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+                token.mint_to(cred_account, amount)?;
+            }
+            ctx.accounts.cred_ledger.record_mint(amount)?;
+        }
+
+        emit!(SlashCompensationClaimed {
+            market: market.key(),
+            user: stake.user,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Paid {} Cred slash compensation to {}", amount, stake.user);
+        Ok(())
+    }
+
+    /// Open a market's sponsor escrow with a first deposit. Without an SPL `mint` the
+    /// escrow holds SOL itself; with one, tokens are held in a vault the escrow owns.
+    /// Unless the market reserved a sponsor, its creator must be or approve the sponsor.
+    pub fn initialize_sponsor_escrow(
+        ctx: Context<InitializeSponsorEscrow>,
+        amount: u64,
+        sponsor_name: String,
+        metadata_uri: String,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(sponsor_name.len() <= MAX_SPONSOR_NAME_LEN, ErrorCode::SponsorNameTooLong);
+        require!(metadata_uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        let sponsor = ctx.accounts.sponsor.key();
+        match market.sponsor {
+            Some(reserved) => require_keys_eq!(reserved, sponsor, ErrorCode::UnauthorizedSponsor),
+            // The escrow PDA is one per market, so nobody may claim it without the creator
+            None => require!(
+                sponsor == market.creator
+                    || ctx.accounts.creator.as_ref().is_some_and(|creator| creator.key() == market.creator),
+                ErrorCode::UnauthorizedSponsor
+            ),
+        }
+
+        let escrow = &mut ctx.accounts.sponsor_escrow;
+        escrow.market = market.key();
+        escrow.sponsor = sponsor;
+        escrow.mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+        escrow.sponsor_name = sponsor_name;
+        escrow.metadata_uri = metadata_uri;
+        escrow.amount = amount;
+        escrow.distributed = 0;
+        escrow.is_released = false;
+        escrow.created_at = Clock::get()?.unix_timestamp;
+        escrow.bump = ctx.bumps.sponsor_escrow;
+
+        match SponsorToken::load(
+            escrow,
+            ctx.accounts.mint.as_deref(),
+            ctx.accounts.escrow_vault.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            Some(token) => token.deposit(
+                ctx.accounts.sponsor_token_account.as_deref(),
+                ctx.accounts.sponsor.to_account_info(),
+                amount,
+            )?,
+            None => system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sponsor.to_account_info(),
+                        to: escrow.to_account_info(),
+                    },
+                ),
+                amount,
+            )?,
+        }
+
+        emit!(SponsorEscrowFunded {
+            market: escrow.market,
+            sponsor,
+            mint: escrow.mint,
+            amount,
+            total: escrow.amount,
+            timestamp: escrow.created_at,
+        });
+
+        msg!("Sponsor escrow opened for market {} with {}", escrow.market, amount);
+        Ok(())
+    }
+
+    /// Add to a sponsor escrow while its market is still open (sponsor only)
+    pub fn deposit_sponsor_escrow(ctx: Context<DepositSponsorEscrow>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(ctx.accounts.market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+
+        let escrow = &mut ctx.accounts.sponsor_escrow;
+        escrow.amount = escrow.amount.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        match SponsorToken::load(
+            escrow,
+            ctx.accounts.mint.as_deref(),
+            ctx.accounts.escrow_vault.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            Some(token) => token.deposit(
+                ctx.accounts.sponsor_token_account.as_deref(),
+                ctx.accounts.sponsor.to_account_info(),
+                amount,
+            )?,
+            None => system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.sponsor.to_account_info(),
+                        to: escrow.to_account_info(),
+                    },
+                ),
+                amount,
+            )?,
+        }
+
+        emit!(SponsorEscrowFunded {
+            market: escrow.market,
+            sponsor: escrow.sponsor,
+            mint: escrow.mint,
+            amount,
+            total: escrow.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Sponsor escrow for market {} now holds {}", escrow.market, escrow.amount);
+        Ok(())
+    }
+
+    /// Pay a winning Cred stake its share of the sponsor escrow once the market is final
+    /// (permissionless). Shares are pro-rata to stake, weighted by the settlement.
+    pub fn claim_sponsor_reward(ctx: Context<ClaimSponsorReward>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let escrow = &mut ctx.accounts.sponsor_escrow;
+        require!(!escrow.is_released, ErrorCode::SponsorEscrowReleased);
+        let now = Clock::get()?.unix_timestamp;
+        resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, now)?;
+        require!(market.outcome != Some(OUTCOME_INVALID), ErrorCode::NotAWinningStake);
+
+        let stake = &mut ctx.accounts.cred_stake;
+        require!(!stake.sponsor_claimed, ErrorCode::SponsorRewardAlreadyClaimed);
+        let reward = winner_share(market, escrow.amount, stake.amount, stake.direction)?;
+        require!(reward > 0, ErrorCode::NotAWinningStake);
+        stake.sponsor_claimed = true;
+        escrow.distributed = escrow.distributed.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+
+        match SponsorToken::load(
+            escrow,
+            ctx.accounts.mint.as_deref(),
+            ctx.accounts.escrow_vault.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            Some(token) => token.pay(escrow, ctx.accounts.staker_token_account.as_deref(), &stake.user, reward)?,
+            None => {
+                escrow.sub_lamports(reward)?;
+                ctx.accounts.staker.add_lamports(reward)?;
+            }
+        }
+
+        emit!(SponsorRewardClaimed {
+            market: market.key(),
+            user: stake.user,
+            mint: escrow.mint,
+            amount: reward,
+            timestamp: now,
+        });
+
+        msg!("Paid {} sponsor reward to {} on market {}", reward, stake.user, market.key());
+        Ok(())
+    }
+
+    /// Return what is left in a sponsor escrow to the sponsor when its market is voided,
+    /// or final with an Invalid outcome or no winning stake (sponsor only). Otherwise the
+    /// unclaimed remainder is returned once `market_pool_claim_window` has passed, which
+    /// also ends claims. The escrow is closed and its rent returned to the sponsor.
+    pub fn refund_sponsor_escrow(ctx: Context<RefundSponsorEscrow>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let escrow = &mut ctx.accounts.sponsor_escrow;
+        require!(!escrow.is_released, ErrorCode::SponsorEscrowReleased);
+        let config = &ctx.accounts.protocol_config;
+        let now = Clock::get()?.unix_timestamp;
+        let refundable = market.status == MarketStatus::Voided
+            || (resolution_upheld(market, config.dispute_window, now).is_ok()
+                && (market.outcome == Some(OUTCOME_INVALID)
+                    || winning_weight(market)? == 0
+                    || now >= market.resolved_at.saturating_add(config.market_pool_claim_window)));
+        require!(refundable, ErrorCode::SponsorEscrowNotRefundable);
+
+        let amount = escrow.amount.checked_sub(escrow.distributed).ok_or(ErrorCode::Overflow)?;
+        escrow.is_released = true;
+
+        match SponsorToken::load(
+            escrow,
+            ctx.accounts.mint.as_deref(),
+            ctx.accounts.escrow_vault.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            Some(token) => {
+                token.pay(escrow, ctx.accounts.sponsor_token_account.as_deref(), &escrow.sponsor, amount)?;
+                token.close(escrow, ctx.accounts.sponsor.to_account_info())?;
+            }
+            None => {
+                escrow.sub_lamports(amount)?;
+                ctx.accounts.sponsor.add_lamports(amount)?;
+            }
+        }
+
+        emit!(SponsorEscrowRefunded {
+            market: market.key(),
+            sponsor: escrow.sponsor,
+            mint: escrow.mint,
+            amount,
+            timestamp: now,
+        });
+
+        msg!("Refunded {} to sponsor {} for market {}", amount, escrow.sponsor, market.key());
+        Ok(())
+    }
+
+    /// Stake Cred on the Oracle's accuracy: `backs_oracle` bets the AI's resolution will
+    /// stand, otherwise the stake is a short betting it will be overturned on dispute
+    pub fn stake_on_oracle(
+        ctx: Context<StakeOnOracle>,
+        amount: u64,
+        backs_oracle: bool,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let vault = &mut ctx.accounts.reputation_vault;
+        let market = &mut ctx.accounts.market;
+        
+        require!(market.status == MarketStatus::Open, ErrorCode::MarketNotOpen);
+        require!(vault.cred_balance >= amount, ErrorCode::InsufficientCred);
+
+        // Deduct from vault
+        vault.cred_balance = vault.cred_balance.checked_sub(amount).ok_or(ErrorCode::Overflow)?;
+        vault.spend_faucet_first(amount);
+        vault.total_staked = vault.total_staked.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+
+        // Create oracle stake record
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        oracle_stake.user = ctx.accounts.user.key();
+        oracle_stake.market = market.key();
+        oracle_stake.amount = amount;
+        oracle_stake.backs_oracle = backs_oracle;
+        oracle_stake.timestamp = Clock::get()?.unix_timestamp;
+        oracle_stake.claimed = false;
+        oracle_stake.bump = ctx.bumps.oracle_stake;
+
+        if backs_oracle {
+            market.oracle_for_stake = market.oracle_for_stake.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        } else {
+            market.oracle_against_stake = market.oracle_against_stake.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        }
+
+        // Token mode: oracle stakes are burned, and winners are minted their reward
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.burn(cred_account, ctx.accounts.user.to_account_info(), &[], amount)?;
+        }
+        ctx.accounts.cred_ledger.record_burn(amount)?;
+
+        emit!(OracleStaked {
+            market: market.key(),
+            user: oracle_stake.user,
+            amount,
+            backs_oracle,
+            timestamp: oracle_stake.timestamp,
+        });
+
+        msg!(
+            "Staked {} Cred {} the Oracle for market {}",
+            amount,
+            if backs_oracle { "on" } else { "against" },
+            market.key()
+        );
+        Ok(())
+    }
+
+    /// Settle an Oracle stake once the market is final (permissionless). Both sides of
+    /// a market's oracle pool share it pari-mutuel: the resolution stands if it was never
+    /// disputed within the window or the dispute was upheld, otherwise the shorts win.
+    /// A side with no counterparty is paid at odds priced from the executor's record.
+    /// The house edge on winnings, and whatever a low-confidence verdict withholds from
+    /// them, is routed to the InsightPool's current budget.
+    pub fn resolve_oracle_stake(ctx: Context<ResolveOracleStake>) -> Result<()> {
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        let market = &ctx.accounts.market;
+        let vault = &mut ctx.accounts.reputation_vault;
+        
+        require!(!oracle_stake.claimed, ErrorCode::OracleStakeAlreadyClaimed);
+        let now = Clock::get()?.unix_timestamp;
+        let upheld = resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, now)?;
+
+        oracle_stake.claimed = true;
+        
+        // Invalid resolutions follow the configured policy
+        let oracle_won = if market.outcome == Some(OUTCOME_INVALID) {
+            upheld && ctx.accounts.protocol_config.invalid_oracle_policy == InvalidOraclePolicy::StakersWin
+        } else {
+            upheld
+        };
+        let won = oracle_stake.backs_oracle == oracle_won;
+        let (reward, house_fee, withheld) = oracle_payout(
+            market,
+            oracle_stake.amount,
+            oracle_stake.backs_oracle,
+            oracle_won,
+            ctx.accounts.agent_executor.upheld_bps()?,
+            ctx.accounts.protocol_config.oracle_house_edge_bps,
+        )?;
+
+        if house_fee > 0 || withheld > 0 {
+            let pool = &mut ctx.accounts.insight_pool;
+            let to_pool = house_fee.checked_add(withheld).ok_or(ErrorCode::Overflow)?;
+            pool.remaining_budget = pool.remaining_budget.checked_add(to_pool).ok_or(ErrorCode::Overflow)?;
+            pool.house_edge_collected = pool.house_edge_collected.checked_add(house_fee).ok_or(ErrorCode::Overflow)?;
+        }
+
+        // Oracle stakes were burned when placed, so the payout is minted back
+        if reward > 0 {
+            vault.cred_balance = vault.cred_balance.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+            vault.total_earned = vault.total_earned.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+
+            if let Some(token) = CredToken::load(
+                &ctx.accounts.protocol_config,
+                ctx.accounts.cred_mint.as_deref(),
+                ctx.accounts.token_program.as_ref(),
+            )? {
+                let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+                token.mint_to(cred_account, reward)?;
+            }
+            ctx.accounts.cred_ledger.record_mint(reward)?;
+        }
+
+        emit!(OracleStakeResolved {
+            market: market.key(),
+            user: oracle_stake.user,
+            amount: oracle_stake.amount,
+            backs_oracle: oracle_stake.backs_oracle,
+            reward,
+            house_fee,
+            withheld,
+            won,
+            timestamp: now,
+        });
+
+        // Score the position as a 0%/100% forecast that the resolution stands
+        let forecast_bps = if oracle_stake.backs_oracle { BPS_DENOMINATOR } else { 0 };
+        let outcome_bps = if oracle_won { BPS_DENOMINATOR } else { 0 };
+        record_settlement(vault, forecast_bps, outcome_bps, oracle_stake.amount)?;
+        record_season_settlement(
+            ctx.accounts.season.as_ref(),
+            ctx.accounts.season_stats.as_mut(),
+            &vault.owner,
+            forecast_bps,
+            outcome_bps,
+            oracle_stake.amount,
+            reward,
+        )?;
+
+        msg!(
+            "Oracle stake resolved: {} {} and received {} Cred",
+            oracle_stake.user,
+            if won { "won" } else { "lost" },
+            reward
+        );
+
+        let token = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        unlock_grant_tranche(
+            &ctx.accounts.protocol_config,
+            &mut ctx.accounts.cred_ledger,
+            vault,
+            oracle_stake.amount,
+            token.as_ref(),
+            ctx.accounts.cred_account.as_deref(),
+        )?;
+        
+        Ok(())
+    }
+
+    /// Refund an Oracle stake at par on a voided market (permissionless). The stake was
+    /// burned when placed, so the refund is minted back; nothing is scored.
+    pub fn refund_oracle_stake(ctx: Context<RefundOracleStake>) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Voided, ErrorCode::MarketNotVoided);
+
+        let oracle_stake = &mut ctx.accounts.oracle_stake;
+        require!(!oracle_stake.claimed, ErrorCode::OracleStakeAlreadyClaimed);
+        oracle_stake.claimed = true;
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        vault.cred_balance = vault.cred_balance.checked_add(oracle_stake.amount).ok_or(ErrorCode::Overflow)?;
+        vault.total_staked = vault.total_staked.saturating_sub(oracle_stake.amount);
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, oracle_stake.amount)?;
+        }
+        ctx.accounts.cred_ledger.record_mint(oracle_stake.amount)?;
+
+        emit!(OracleStakeRefunded {
+            market: market.key(),
+            user: oracle_stake.user,
+            amount: oracle_stake.amount,
+            backs_oracle: oracle_stake.backs_oracle,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Refunded {} Cred Oracle stake to {} for voided market {}", oracle_stake.amount, oracle_stake.user, market.key());
+        Ok(())
+    }
+
+    /// Refund a booster's market pool contribution on a market voided or finally resolved
+    /// as Invalid (permissionless). Boosts were burned when made, so the refund is minted back.
+    pub fn refund_market_boost(ctx: Context<RefundMarketBoost>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(
+            matches!(market.status, MarketStatus::Resolved | MarketStatus::Voided),
+            ErrorCode::MarketNotResolved
+        );
+        require!(market.outcome == Some(OUTCOME_INVALID), ErrorCode::MarketNotInvalid);
+        // An Invalid verdict can still be overturned; a voided market is final
+        if market.status == MarketStatus::Resolved {
+            resolution_upheld(market, ctx.accounts.protocol_config.dispute_window, Clock::get()?.unix_timestamp)?;
+        }
+
+        let boost = &mut ctx.accounts.market_boost;
+        require!(!boost.refunded, ErrorCode::BoostAlreadyRefunded);
+        boost.refunded = true;
+        market.market_pool_refunded = market.market_pool_refunded.checked_add(boost.amount).ok_or(ErrorCode::Overflow)?;
+
+        let vault = &mut ctx.accounts.reputation_vault;
+        vault.cred_balance = vault.cred_balance.checked_add(boost.amount).ok_or(ErrorCode::Overflow)?;
+
+        if let Some(token) = CredToken::load(
+            &ctx.accounts.protocol_config,
+            ctx.accounts.cred_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )? {
+            let cred_account = token.vault_account(ctx.accounts.cred_account.as_deref(), vault)?;
+            token.mint_to(cred_account, boost.amount)?;
+        }
+        ctx.accounts.cred_ledger.record_mint(boost.amount)?;
+
+        emit!(MarketBoostRefunded {
+            market: market.key(),
+            booster: boost.booster,
+            amount: boost.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Refunded {} Cred boost to {} for market {}", boost.amount, boost.booster, market.key());
+        Ok(())
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Whether a market's resolution stood, once it is final. A resolution is final when
+/// its dispute was settled, or when the dispute window passed without one.
+pub fn resolution_upheld(market: &Market, dispute_window: i64, now: i64) -> Result<bool> {
+    match (market.status, market.dispute_status) {
+        (MarketStatus::Resolved, DisputeStatus::Upheld) => Ok(true),
+        (MarketStatus::Resolved, DisputeStatus::Overturned) => Ok(false),
+        (MarketStatus::Resolved, DisputeStatus::None)
+            if now >= market.resolved_at.saturating_add(dispute_window) =>
+        {
+            Ok(true)
+        }
+        _ => err!(ErrorCode::MarketNotFinal),
+    }
+}
+
+/// Scale a reward by the resolver's confidence. Verdicts at or above
+/// `FULL_REWARD_CONFIDENCE_BPS` pay in full; below it the reward falls
+/// linearly to 0 at 0% confidence, so there is no jump at the threshold.
+pub fn scale_by_confidence(amount: u64, confidence_bps: u16) -> Result<u64> {
+    if confidence_bps >= FULL_REWARD_CONFIDENCE_BPS {
+        return Ok(amount);
+    }
+    let scaled = (amount as u128)
+        .checked_mul(confidence_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / FULL_REWARD_CONFIDENCE_BPS as u128;
+    u64::try_from(scaled).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// InsightPool emission for a zero-based epoch: `base` reduced by `decay_bps`
+/// once per epoch, compounding.
+pub fn decayed_emission(base: u64, decay_bps: u16, epoch: u64) -> Result<u64> {
+    const SCALE: u128 = 1_000_000_000_000;
+    let retained_bps = BPS_DENOMINATOR.saturating_sub(decay_bps) as u128;
+    let mut factor = retained_bps * SCALE / BPS_DENOMINATOR as u128;
+    let mut multiplier = SCALE;
+    let mut remaining = epoch;
+    while remaining > 0 && multiplier > 0 {
+        if remaining & 1 == 1 {
+            multiplier = multiplier * factor / SCALE;
+        }
+        factor = factor * factor / SCALE;
+        remaining >>= 1;
+    }
+    let emission = (base as u128) * multiplier / SCALE;
+    u64::try_from(emission).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Pari-mutuel payout for a stake. The YES side shares `settlement_bps` of the
+/// total pool pro-rata, the NO side shares the remainder. A one-sided market has no
+/// counterparty, so every stake is returned at par whatever the settlement.
+pub fn settlement_payout(market: &Market, amount: u64, direction: bool) -> Result<u64> {
+    if market.total_yes_stake == 0 || market.total_no_stake == 0 {
+        return Ok(amount);
+    }
+    let total_pool = (market.total_yes_stake as u128)
+        .checked_add(market.total_no_stake as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let (side_bps, side_total) = if direction {
+        (market.settlement_bps as u128, market.total_yes_stake as u128)
+    } else {
+        ((BPS_DENOMINATOR - market.settlement_bps) as u128, market.total_no_stake as u128)
+    };
+    if side_total == 0 {
+        return Ok(0);
+    }
+    let payout = total_pool
+        .checked_mul(side_bps)
+        .and_then(|v| v.checked_mul(amount as u128))
+        .ok_or(ErrorCode::Overflow)?
+        / (BPS_DENOMINATOR as u128 * side_total);
+    u64::try_from(payout).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Total settlement-weighted Cred stake on a market: each side's stake times the share
+/// of the pool its settlement awards it. Zero when no stake is on a winning side.
+pub fn winning_weight(market: &Market) -> Result<u128> {
+    let yes = (market.total_yes_stake as u128)
+        .checked_mul(market.settlement_bps as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    let no = (market.total_no_stake as u128)
+        .checked_mul((BPS_DENOMINATOR - market.settlement_bps) as u128)
+        .ok_or(ErrorCode::Overflow)?;
+    yes.checked_add(no).ok_or(ErrorCode::Overflow.into())
+}
+
+/// A Cred stake's share of a reward `pot` split among a market's winners, pro-rata to
+/// stake and weighted by the settlement
+pub fn winner_share(market: &Market, pot: u64, amount: u64, direction: bool) -> Result<u64> {
+    let total = winning_weight(market)?;
+    if total == 0 {
+        return Ok(0);
+    }
+    let side_bps = if direction {
+        market.settlement_bps
+    } else {
+        BPS_DENOMINATOR - market.settlement_bps
+    };
+    let share = (pot as u128)
+        .checked_mul(amount as u128)
+        .and_then(|v| v.checked_mul(side_bps as u128))
+        .ok_or(ErrorCode::Overflow)?
+        / total;
+    u64::try_from(share).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Set a market's resolution and emit the resolution events
+pub fn apply_resolution(market: &mut Account<Market>, resolution: Resolution, resolver: Pubkey, timestamp: i64) -> Result<()> {
+    let Resolution {
+        outcome,
+        ipfs_transcript_hash,
+        confidence_bps,
+        model_id,
+        researcher_hash,
+        judge_hash,
+        settlement_bps,
+    } = resolution;
+
+    market.status = MarketStatus::Resolved;
+    market.outcome = Some(outcome);
+    market.resolved_at = timestamp;
+    market.ipfs_transcript_hash = ipfs_transcript_hash;
+    market.confidence_bps = confidence_bps;
+    market.model_id = model_id.clone();
+    market.researcher_hash = researcher_hash;
+    market.judge_hash = judge_hash;
+    market.settlement_bps = match (settlement_bps, outcome) {
+        (Some(bps), _) => bps,
+        (None, OUTCOME_YES) => BPS_DENOMINATOR,
+        _ => 0,
+    };
+
+    emit!(MarketResolved {
+        market: market.key(),
+        outcome,
+        ipfs_transcript_hash,
+        confidence_bps,
+        model_id,
+        researcher_hash,
+        judge_hash,
+        settlement_bps: market.settlement_bps,
+        resolver,
+        timestamp,
+    });
+
+    // Emit NFT mint request (invalid markets have no verdict to prove)
+    if outcome != OUTCOME_INVALID {
+        emit!(ProofNFTMintRequested {
+            market: market.key(),
+            outcome,
+            ipfs_transcript_hash,
+            timestamp,
+        });
+    }
+    Ok(())
+}
+
+/// Check that the instruction just before the current one is an Ed25519 program
+/// instruction verifying exactly one signature by `signer` over `message`, with all
+/// of its data contained in that instruction.
+pub fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::MissingSignatureVerification);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::MissingSignatureVerification);
+
+    // Header: signature count and padding, then one offsets record of seven u16s
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidSignedVerdict);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let (signature_ix, pubkey_offset, pubkey_ix) = (read_u16(4), read_u16(6) as usize, read_u16(8));
+    let (message_offset, message_size, message_ix) = (read_u16(10) as usize, read_u16(12) as usize, read_u16(14));
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        ErrorCode::InvalidSignedVerdict
+    );
+
+    let pubkey = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ErrorCode::InvalidSignedVerdict)?;
+    let signed = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignedVerdict)?;
+    require!(pubkey == signer.as_ref(), ErrorCode::InvalidSignedVerdict);
+    require!(signed == message, ErrorCode::InvalidSignedVerdict);
+    Ok(())
+}
+
+/// Where an ensemble market's verdicts stand. An outcome wins once it has
+/// `ensemble_quorum` verdicts; the ensemble is split once no outcome can still get there.
+pub fn ensemble_decision(market: &Market) -> EnsembleDecision {
+    let remaining = market.ensemble_size.saturating_sub(market.verdict_count);
+    let mut reachable = false;
+    for outcome in 0..=OUTCOME_INVALID {
+        let tally = market.verdict_tallies[outcome as usize];
+        if tally >= market.ensemble_quorum {
+            return EnsembleDecision::Quorum(outcome);
+        }
+        reachable |= tally.saturating_add(remaining) >= market.ensemble_quorum;
+    }
+    if reachable {
+        EnsembleDecision::Pending
+    } else {
+        EnsembleDecision::Split
+    }
+}
+
+/// `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Cred staked against the original verdict, i.e. by the stakers an overturned
+/// resolution wronged. Invalid resolutions refund everyone, so nobody is affected.
+pub fn slash_affected_stake(market: &Market) -> u64 {
+    match market.overturned_outcome {
+        Some(OUTCOME_YES) => market.total_no_stake,
+        Some(OUTCOME_NO) => market.total_yes_stake,
+        _ => 0,
+    }
+}
+
+/// Payout and house fee for an oracle stake. The side that called the resolution
+/// correctly shares the other side's stakes pro-rata. If nobody took the other side,
+/// the house is the counterparty at fair odds from `upheld_bps`, the executor's
+/// historical chance of being upheld. The house edge is taken from the winnings, and
+/// the rest is scaled by the resolver's confidence. Returns the payout, the house fee
+/// and the winnings withheld for low confidence.
+pub fn oracle_payout(
+    market: &Market,
+    amount: u64,
+    backs_oracle: bool,
+    oracle_won: bool,
+    upheld_bps: u16,
+    house_edge_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    if backs_oracle != oracle_won {
+        return Ok((0, 0, 0));
+    }
+    let (winning_total, losing_total) = if oracle_won {
+        (market.oracle_for_stake, market.oracle_against_stake)
+    } else {
+        (market.oracle_against_stake, market.oracle_for_stake)
+    };
+
+    let winnings = if losing_total > 0 {
+        (losing_total as u128)
+            .checked_mul(amount as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / winning_total as u128
+    } else {
+        // Fair odds on the winning side: stake * (1 - p) / p
+        let side_bps = if oracle_won { upheld_bps } else { BPS_DENOMINATOR - upheld_bps };
+        (amount as u128)
+            .checked_mul((BPS_DENOMINATOR - side_bps) as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / side_bps as u128
+    };
+    let house_fee = winnings
+        .checked_mul(house_edge_bps as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / BPS_DENOMINATOR as u128;
+    let net_winnings = u64::try_from(winnings - house_fee).map_err(|_| ErrorCode::Overflow)?;
+    let reward = scale_by_confidence(net_winnings, market.confidence_bps)?;
+    let payout = amount.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+
+    Ok((
+        payout,
+        u64::try_from(house_fee).map_err(|_| ErrorCode::Overflow)?,
+        net_winnings - reward,
+    ))
+}
+
+/// Check a market's Cred flows and return what its escrow holds. Stakes in must equal
+/// payouts plus refunds plus the remaining escrow (the token escrow's balance when one
+/// is passed), and boosts into the market pool must equal its payouts plus the swept
+/// or still claimable remainder. An Invalid market only refunds and a valid one only
+/// pays out, and no market can exceed the ledger totals.
+pub fn audit_market_flows(market: &Market, ledger: &CredLedger, escrow_balance: Option<u64>) -> Result<u64> {
+    let total_stake = market
+        .total_yes_stake
+        .checked_add(market.total_no_stake)
+        .ok_or(ErrorCode::InvariantViolation)?;
+    let released = market
+        .total_paid_out
+        .checked_add(market.total_refunded)
+        .ok_or(ErrorCode::InvariantViolation)?;
+    let escrowed = match escrow_balance {
+        Some(balance) => balance,
+        None => total_stake.checked_sub(released).ok_or(ErrorCode::InvariantViolation)?,
+    };
+    require_eq!(
+        total_stake,
+        released.checked_add(escrowed).ok_or(ErrorCode::InvariantViolation)?,
+        ErrorCode::InvariantViolation
+    );
+    require!(market.settled_stake <= total_stake, ErrorCode::InvariantViolation);
+
+    let pool_claimable = if market.market_pool_swept {
+        0
+    } else {
+        market
+            .market_pool
+            .checked_sub(market.market_pool_paid_out)
+            .and_then(|v| v.checked_sub(market.market_pool_refunded))
+            .ok_or(ErrorCode::InvariantViolation)?
+    };
+    require_eq!(
+        market.market_pool,
+        market
+            .market_pool_paid_out
+            .checked_add(market.market_pool_swept_amount)
+            .and_then(|v| v.checked_add(market.market_pool_refunded))
+            .and_then(|v| v.checked_add(pool_claimable))
+            .ok_or(ErrorCode::InvariantViolation)?,
+        ErrorCode::InvariantViolation
+    );
+
+    match market.outcome {
+        None => require!(
+            released == 0 && market.settled_stake == 0 && market.insight_pool_amount == 0,
+            ErrorCode::InvariantViolation
+        ),
+        Some(OUTCOME_INVALID) => require!(
+            market.total_paid_out == 0
+                && market.insight_pool_amount == 0
+                && market.total_refunded == market.settled_stake,
+            ErrorCode::InvariantViolation
+        ),
+        Some(_) => require!(
+            market.total_refunded == 0 && (market.settled_stake > 0 || market.total_paid_out == 0),
+            ErrorCode::InvariantViolation
+        ),
+    }
+
+    require!(
+        total_stake <= ledger.total_staked
+            && market.total_paid_out <= ledger.total_paid_out
+            && market.total_refunded <= ledger.total_refunded
+            && market.insight_pool_amount <= ledger.total_minted,
+        ErrorCode::InvariantViolation
+    );
+
+    // Escrowed Cred is part of the outstanding supply
+    require!(
+        ledger
+            .escrowed()
+            .zip(ledger.outstanding())
+            .is_some_and(|(escrowed, outstanding)| escrowed <= outstanding),
+        ErrorCode::InvariantViolation
+    );
+    Ok(escrowed)
+}
+
+/// Score a forecast against a settlement. A forecast within 50% of the outcome
+/// is a win (`Some(true)`), one further away a loss (`Some(false)`). Also returns
+/// the Brier score in basis points (0 = perfect, 10000 = worst).
+pub fn score_forecast(forecast_bps: u16, outcome_bps: u16) -> (Option<bool>, u64) {
+    let error = forecast_bps.abs_diff(outcome_bps) as u64;
+    let half = BPS_DENOMINATOR as u64 / 2;
+    let won = match error.cmp(&half) {
+        std::cmp::Ordering::Less => Some(true),
+        std::cmp::Ordering::Greater => Some(false),
+        std::cmp::Ordering::Equal => None,
+    };
+    (won, error * error / BPS_DENOMINATOR as u64)
+}
+
+/// Update a vault's accuracy stats for a settled stake of `staked` Cred and emit
+/// `ReputationUpdated`. The Brier score of the forecast is added to the vault's running total.
+pub fn record_settlement(
+    vault: &mut ReputationVault,
+    forecast_bps: u16,
+    outcome_bps: u16,
+    staked: u64,
+) -> Result<()> {
+    let (won, brier) = score_forecast(forecast_bps, outcome_bps);
+    match won {
+        Some(true) => vault.wins = vault.wins.checked_add(1).ok_or(ErrorCode::Overflow)?,
+        Some(false) => vault.losses = vault.losses.checked_add(1).ok_or(ErrorCode::Overflow)?,
+        None => {}
+    }
+    vault.settled_count = vault.settled_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    vault.settled_stake = vault.settled_stake.checked_add(staked).ok_or(ErrorCode::Overflow)?;
+    vault.brier_score_total = vault.brier_score_total.checked_add(brier).ok_or(ErrorCode::Overflow)?;
+
+    emit!(ReputationUpdated {
+        user: vault.owner,
+        wins: vault.wins,
+        losses: vault.losses,
+        settled_count: vault.settled_count,
+        brier_score_total: vault.brier_score_total,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Record a settled stake against the user's stats for the active Season. Both
+/// accounts are optional; nothing is recorded when they are omitted or the
+/// Season has already run out.
+pub fn record_season_settlement(
+    season: Option<&Account<Season>>,
+    stats: Option<&mut Account<SeasonStats>>,
+    owner: &Pubkey,
+    forecast_bps: u16,
+    outcome_bps: u16,
+    amount: u64,
+    payout: u64,
+) -> Result<()> {
+    let (Some(season), Some(stats)) = (season, stats) else {
+        return Ok(());
+    };
+    require!(
+        stats.owner == *owner && stats.season == season.key(),
+        ErrorCode::SeasonStatsMismatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    if season.status != SeasonStatus::Active || now >= season.end_ts {
+        return Ok(());
+    }
+
+    let (won, brier) = score_forecast(forecast_bps, outcome_bps);
+    match won {
+        Some(true) => stats.wins = stats.wins.checked_add(1).ok_or(ErrorCode::Overflow)?,
+        Some(false) => stats.losses = stats.losses.checked_add(1).ok_or(ErrorCode::Overflow)?,
+        None => {}
+    }
+    stats.settled_count = stats.settled_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    stats.brier_score_total = stats.brier_score_total.checked_add(brier).ok_or(ErrorCode::Overflow)?;
+    stats.cred_staked = stats.cred_staked.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    stats.cred_earned = stats.cred_earned.checked_add(payout).ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+/// Reset a Season account to an empty, active season starting at `start_ts`
+pub fn open_season(season: &mut Season, number: u32, start_ts: i64, duration: i64, bump: u8) -> Result<()> {
+    season.number = number;
+    season.start_ts = start_ts;
+    season.end_ts = start_ts.checked_add(duration).ok_or(ErrorCode::Overflow)?;
+    season.duration = duration;
+    season.status = SeasonStatus::Active;
+    season.leaderboard = Vec::new();
+    season.bump = bump;
+
+    emit!(SeasonStarted {
+        number,
+        start_ts,
+        end_ts: season.end_ts,
+    });
+    Ok(())
+}
+
+/// Insert or update `owner` with `score` in a ranked list, keeping the top `LEADERBOARD_SIZE`
+pub fn insert_ranked(entries: &mut Vec<LeaderboardEntry>, owner: Pubkey, score: u64) {
+    entries.retain(|e| e.owner != owner);
+    if score > 0 {
+        // Insert after existing entries with an equal score so incumbents keep their rank
+        let index = entries.partition_point(|e| e.score >= score);
+        entries.insert(index, LeaderboardEntry { owner, score });
+        entries.truncate(LEADERBOARD_SIZE);
+    }
+}
+
+/// Zero-based rank of `owner` in a ranked list
+pub fn rank_in(entries: &[LeaderboardEntry], owner: &Pubkey) -> Option<usize> {
+    entries.iter().position(|e| e.owner == *owner)
+}
+
+/// Enforce an identity attestation when the protocol requires one for an action
+pub fn require_attestation(required: bool, attestation: Option<&Account<Attestation>>) -> Result<()> {
+    if !required {
+        return Ok(());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        attestation.is_some_and(|attestation| attestation.is_valid(now)),
+        ErrorCode::AttestationRequired
+    );
+    Ok(())
+}
+
+/// Release one tranche of a vault's locked initial grant after a settled stake. The
+/// tranche never exceeds the settled amount, so dust stakes cannot farm the grant.
+pub fn unlock_grant_tranche<'info>(
+    config: &ProtocolConfig,
+    ledger: &mut CredLedger,
+    vault: &mut ReputationVault,
+    staked: u64,
+    token: Option<&CredToken<'_, 'info>>,
+    cred_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<()> {
+    let tranche = vault.locked_grant.min(config.grant_tranche).min(staked);
+    if tranche == 0 {
+        return Ok(());
+    }
+    vault.locked_grant -= tranche;
+    vault.cred_balance = vault.cred_balance.checked_add(tranche).ok_or(ErrorCode::Overflow)?;
+    vault.total_earned = vault.total_earned.checked_add(tranche).ok_or(ErrorCode::Overflow)?;
+
+    if let Some(token) = token {
+        let cred_account = token.vault_account(cred_account, vault)?;
+        token.mint_to(cred_account, tranche)?;
+    }
+
+    vault.record_earned(EarnMethod::InitialGrant, tranche)?;
+    ledger.record_mint(tranche)?;
+
+    emit!(CredEarned {
+        user: vault.owner,
+        amount: tranche,
+        method: EarnMethod::InitialGrant,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Whether a referee has settled at least `referral_min_stake` Cred of stakes and their
+/// referrer is still owed a bonus
+pub fn referral_bonus_due(config: &ProtocolConfig, referee: &ReputationVault) -> bool {
+    referee.referrer.is_some()
+        && !referee.referral_bonus_paid
+        && referee.settled_count > 0
+        && referee.settled_stake >= config.referral_min_stake
+}
+
+/// Credit the configured referral bonus to `referrer` and mark it paid on `referee`
+pub fn pay_referral_bonus<'info>(
+    config: &ProtocolConfig,
+    ledger: &mut CredLedger,
+    referee: &mut ReputationVault,
+    referrer: &mut ReputationVault,
+    token: Option<&CredToken<'_, 'info>>,
+    referrer_cred_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<()> {
+    require!(referee.referrer == Some(referrer.owner), ErrorCode::ReferrerMismatch);
+    referee.referral_bonus_paid = true;
+
+    let bonus = config.referral_bonus;
+    if bonus == 0 {
+        return Ok(());
+    }
+    referrer.cred_balance = referrer.cred_balance.checked_add(bonus).ok_or(ErrorCode::Overflow)?;
+    referrer.total_earned = referrer.total_earned.checked_add(bonus).ok_or(ErrorCode::Overflow)?;
+
+    if let Some(token) = token {
+        let cred_account = token.vault_account(referrer_cred_account, referrer)?;
+        token.mint_to(cred_account, bonus)?;
+    }
+
+    referrer.record_earned(EarnMethod::Referral, bonus)?;
+    ledger.record_mint(bonus)?;
+
+    emit!(CredEarned {
+        user: referrer.owner,
+        amount: bonus,
+        method: EarnMethod::Referral,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Referral bonus of {} Cred paid to {}", bonus, referrer.owner);
+    Ok(())
+}
+
+/// Move Cred between two vaults, enforcing the sender's daily transfer window.
+/// Transfers do not count as earned Cred, so they cannot inflate leaderboard scores.
+pub fn move_cred(
+    config: &ProtocolConfig,
+    from: &mut ReputationVault,
+    to: &mut ReputationVault,
+    amount: u64,
+    memo: Option<&str>,
+    now: i64,
+) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(from.owner != to.owner, ErrorCode::SelfTransfer);
+    require!(memo.map_or(0, str::len) <= MAX_MEMO_LEN, ErrorCode::MemoTooLong);
+    require!(from.cred_balance >= amount, ErrorCode::InsufficientCred);
+    require!(from.transferable_balance() >= amount, ErrorCode::FaucetCredNotTransferable);
+
+    let today = now.div_euclid(SECONDS_PER_DAY);
+    if from.transfer_day != today {
+        from.transfer_day = today;
+        from.transferred_today = 0;
+    }
+    let sent_today = from.transferred_today.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    require!(sent_today <= config.daily_transfer_limit, ErrorCode::DailyTransferLimitExceeded);
+    from.transferred_today = sent_today;
+
+    from.cred_balance = from.cred_balance.checked_sub(amount).ok_or(ErrorCode::InsufficientCred)?;
+    to.cred_balance = to.cred_balance.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+    Ok(())
+}
+
+/// Cred token-mode context. Only built when the ProtocolConfig has a Cred mint;
+/// otherwise Cred lives solely in `ReputationVault` balances and the optional
+/// token accounts are ignored.
+pub struct CredToken<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+    pub mint_bump: u8,
+    pub non_transferable: bool,
+}
+
+impl<'a, 'info> CredToken<'a, 'info> {
+    pub fn load(
+        config: &ProtocolConfig,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(cred_mint) = config.cred_mint else {
+            return Ok(None);
+        };
+        let mint = mint.ok_or(ErrorCode::CredTokenAccountsRequired)?;
+        let token_program = token_program.ok_or(ErrorCode::CredTokenAccountsRequired)?;
+        require_keys_eq!(mint.key(), cred_mint, ErrorCode::InvalidCredMint);
+        require_keys_eq!(*mint.to_account_info().owner, token_program.key(), ErrorCode::InvalidCredMint);
+
+        Ok(Some(Self {
+            mint,
+            token_program,
+            mint_bump: config.cred_mint_bump,
+            non_transferable: config.cred_non_transferable,
+        }))
+    }
+
+    /// Check that a Cred token account was passed and belongs to `owner`
+    pub fn owner_account<'b>(
+        &self,
+        account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+        owner: &Pubkey,
+    ) -> Result<&'b InterfaceAccount<'info, TokenAccount>> {
+        let account = account.ok_or(ErrorCode::CredTokenAccountsRequired)?;
+        require_keys_eq!(account.mint, self.mint.key(), ErrorCode::InvalidCredTokenAccount);
+        require_keys_eq!(account.owner, *owner, ErrorCode::InvalidCredTokenAccount);
+        Ok(account)
+    }
+
+    /// Check that a Cred token account was passed for a vault whose balance is backed by tokens
+    pub fn vault_account<'b>(
+        &self,
+        account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+        vault: &ReputationVault,
+    ) -> Result<&'b InterfaceAccount<'info, TokenAccount>> {
+        require!(vault.token_backed, ErrorCode::VaultNotTokenBacked);
+        self.owner_account(account, &vault.owner)
+    }
+
+    /// Check that the market's Cred escrow was passed
+    pub fn escrow_account<'b>(
+        &self,
+        account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+        market: &Pubkey,
+    ) -> Result<&'b InterfaceAccount<'info, TokenAccount>> {
+        let account = account.ok_or(ErrorCode::CredTokenAccountsRequired)?;
+        let (escrow, _) = Pubkey::find_program_address(&[MARKET_ESCROW_SEED, market.as_ref()], &crate::ID);
+        require_keys_eq!(account.key(), escrow, ErrorCode::InvalidCredTokenAccount);
+        Ok(account)
+    }
+
+    pub fn mint_to(&self, to: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        let mint = self.mint.to_account_info();
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: mint.clone(),
+                    to: to.to_account_info(),
+                    authority: mint,
+                },
+                &[&[CRED_MINT_SEED, &[self.mint_bump]]],
+            ),
+            amount,
+        )
+    }
+
+    pub fn burn(
+        &self,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        authority: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority,
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+
+    /// Move Cred between token accounts. A non-transferable mint cannot transfer,
+    /// so the move is a burn from `from` followed by a mint into `to`.
+    pub fn transfer(
+        &self,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        authority: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+    ) -> Result<()> {
+        if self.non_transferable {
+            self.burn(from, authority, signer_seeds, amount)?;
+            return self.mint_to(to, amount);
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority,
+                },
+                signer_seeds,
+            ),
+            amount,
+            CRED_DECIMALS,
+        )
+    }
+}
+
+/// An SPL sponsor escrow's token accounts. `load` returns None for a SOL escrow, and
+/// checks the passed mint and vault belong to the escrow otherwise.
+pub struct SponsorToken<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> SponsorToken<'a, 'info> {
+    pub fn load(
+        escrow: &SponsorEscrow,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(expected) = escrow.mint else {
+            return Ok(None);
+        };
+        match (mint, vault, token_program) {
+            (Some(mint), Some(vault), Some(token_program)) => {
+                require_keys_eq!(mint.key(), expected, ErrorCode::InvalidSponsorAccount);
+                require_keys_eq!(vault.mint, expected, ErrorCode::InvalidSponsorAccount);
+                Ok(Some(Self { mint, vault, token_program }))
+            }
+            _ => err!(ErrorCode::InvalidSponsorAccount),
+        }
+    }
+
+    /// Move `amount` from the sponsor's token account into the escrow vault
+    pub fn deposit(
+        &self,
+        from: Option<&InterfaceAccount<'info, TokenAccount>>,
+        sponsor: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let from = from.ok_or(ErrorCode::InvalidSponsorAccount)?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.vault.to_account_info(),
+                    authority: sponsor,
+                },
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+
+    /// Pay `amount` from the escrow vault to `owner`'s token account for the escrow mint
+    pub fn pay(
+        &self,
+        escrow: &Account<'info, SponsorEscrow>,
+        to: Option<&InterfaceAccount<'info, TokenAccount>>,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let to = to.ok_or(ErrorCode::InvalidSponsorAccount)?;
+        require!(to.owner == *owner && to.mint == self.mint.key(), ErrorCode::InvalidSponsorAccount);
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                &[&[SPONSOR_ESCROW_SEED, escrow.market.as_ref(), &[escrow.bump]]],
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+
+    /// Close the emptied escrow vault, returning its rent to `destination`
+    pub fn close(&self, escrow: &Account<'info, SponsorEscrow>, destination: AccountInfo<'info>) -> Result<()> {
+        token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination,
+                authority: escrow.to_account_info(),
+            },
+            &[&[SPONSOR_ESCROW_SEED, escrow.market.as_ref(), &[escrow.bump]]],
+        ))
+    }
+}
+
+/// Score a vault for a leaderboard metric. Accuracy and calibration are in basis
+/// points and stay at 0 until the vault has `MIN_SETTLED_FOR_RANKING` settlements.
+pub fn leaderboard_score(vault: &ReputationVault, metric: LeaderboardMetric) -> u64 {
+    let bps = BPS_DENOMINATOR as u64;
+    match metric {
+        LeaderboardMetric::NetCredEarned => vault
+            .total_earned
+            .saturating_sub(INITIAL_CRED_GRANT)
+            .saturating_sub(vault.total_staked),
+        LeaderboardMetric::Accuracy => {
+            let decided = vault.wins.saturating_add(vault.losses);
+            if vault.settled_count < MIN_SETTLED_FOR_RANKING || decided == 0 {
+                return 0;
+            }
+            vault.wins.saturating_mul(bps) / decided
+        }
+        LeaderboardMetric::Calibration => {
+            if vault.settled_count < MIN_SETTLED_FOR_RANKING {
+                return 0;
+            }
+            bps.saturating_sub(vault.brier_score_total / vault.settled_count)
+        }
+    }
+}
+
+// ============================================================================
+// ACCOUNTS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeInsightPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + InsightPool::INIT_SPACE,
+        seeds = [INSIGHT_POOL_SEED],
+        bump
+    )]
+    pub insight_pool: Account<'info, InsightPool>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeAgentExecutor<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AgentExecutor::INIT_SPACE,
+        seeds = [AGENT_EXECUTOR_SEED],
+        bump
+    )]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct RegisterExecutor<'info> {
+    #[account(
+        init,
+        payer = config_authority,
+        space = 8 + AgentExecutor::INIT_SPACE,
+        seeds = [AGENT_EXECUTOR_SEED, authority.as_ref()],
+        bump
+    )]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The config authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub config_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetExecutorActive<'info> {
+    #[account(mut)]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolConfig::INIT_SPACE,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCredMint<'info> {
+    /// CHECK: Created and initialized as the Cred mint in the handler
+    #[account(
+        mut,
+        seeds = [CRED_MINT_SEED],
+        bump
+    )]
+    pub cred_mint: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeMarketEscrow<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_MINT_SEED],
+        bump
+    )]
+    pub cred_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [MARKET_ESCROW_SEED, market.key().as_ref()],
+        bump,
+        token::mint = cred_mint,
+        token::authority = market,
+        token::token_program = token_program,
+    )]
+    pub market_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVaultToToken<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_MINT_SEED],
+        bump
+    )]
+    pub cred_mint: Box<InterfaceAccount<'info, Mint>>,
+    
+    /// The vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(attester: Pubkey)]
+pub struct AddAttester<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Attester::INIT_SPACE,
+        seeds = [ATTESTER_SEED, attester.as_ref()],
+        bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAttesterActive<'info> {
+    #[account(
+        mut,
+        seeds = [ATTESTER_SEED, attester_account.attester.as_ref()],
+        bump = attester_account.bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct IssueAttestation<'info> {
+    #[account(
+        init,
+        payer = attester,
+        space = 8 + Attestation::INIT_SPACE,
+        seeds = [ATTESTATION_SEED, attester.key().as_ref(), subject.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    /// CHECK: The attested user
+    pub subject: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [ATTESTER_SEED, attester.key().as_ref()],
+        bump = attester_account.bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    
+    /// The approved attester signer
+    #[account(mut)]
+    pub attester: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [ATTESTATION_SEED, attester.key().as_ref(), attestation.subject.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(
+        seeds = [ATTESTER_SEED, attester.key().as_ref()],
+        bump = attester_account.bump
+    )]
+    pub attester_account: Account<'info, Attester>,
+    
+    /// The attester that issued the attestation
+    pub attester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        mut,
+        close = attester,
+        seeds = [ATTESTATION_SEED, attester.key().as_ref(), attestation.subject.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    /// The attester that issued the attestation, refunded its rent
+    #[account(mut)]
+    pub attester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimIdentityReward<'info> {
+    #[account(
+        mut,
+        seeds = [ATTESTATION_SEED, attestation.attester.as_ref(), reputation_vault.owner.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFaucet<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Faucet::INIT_SPACE,
+        seeds = [FAUCET_SEED],
+        bump
+    )]
+    pub faucet: Account<'info, Faucet>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCredLedger<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CredLedger::INIT_SPACE,
+        seeds = [CRED_LEDGER_SEED],
+        bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFaucet<'info> {
+    #[account(
+        mut,
+        seeds = [FAUCET_SEED],
+        bump = faucet.bump
+    )]
+    pub faucet: Account<'info, Faucet>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, user.key().as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReputationVault<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReputationVault::INIT_SPACE,
+        seeds = [REPUTATION_VAULT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    /// CHECK: The owner of this vault; must sign to receive the full grant or record a referrer
+    pub owner: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Optional: the owner's identity attestation, unlocking the full grant
+    #[account(
+        seeds = [ATTESTATION_SEED, attestation.attester.as_ref(), owner.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+    
+    /// Optional: the vault of the user who referred this owner
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, referrer_vault.owner.as_ref()],
+        bump = referrer_vault.bump
+    )]
+    pub referrer_vault: Option<Account<'info, ReputationVault>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+#[instruction(tweet_url: String, market_id: String)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [MARKET_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    /// The registered executor the creator chooses to resolve this market
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Optional: the creator's identity attestation, when the protocol requires one
+    #[account(
+        seeds = [ATTESTATION_SEED, attestation.attester.as_ref(), creator.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+}
+
+#[derive(Accounts)]
+pub struct StakeCred<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, user.key().as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + CredStake::INIT_SPACE,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token mode: the market's Cred escrow token account
+    #[account(mut)]
+    pub market_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Evidence::INIT_SPACE,
+        seeds = [EVIDENCE_SEED, market.key().as_ref(), &[market.evidence_count.saturating_add(1)]],
+        bump
+    )]
+    pub evidence: Account<'info, Evidence>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut, has_one = agent_executor @ ErrorCode::WrongExecutor)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match agent_executor.authority)
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarketSigned<'info> {
+    #[account(mut, has_one = agent_executor @ ErrorCode::WrongExecutor)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// CHECK: the instructions sysvar, read to find the Ed25519 signature check
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// Anyone may relay a signed verdict
+    pub relayer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSignedVerdicts<'info> {
+    #[account(mut, has_one = agent_executor @ ErrorCode::WrongExecutor)]
+    pub market: Account<'info, Market>,
+    
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match agent_executor.authority)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableEnsemble<'info> {
+    #[account(mut, has_one = creator @ ErrorCode::UnauthorizedCreator)]
+    pub market: Account<'info, Market>,
+    
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitVerdict<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ExecutorVerdict::INIT_SPACE,
+        seeds = [VERDICT_SEED, market.key().as_ref(), agent_executor.key().as_ref()],
+        bump
+    )]
+    pub verdict: Account<'info, ExecutorVerdict>,
+    
+    /// One of the market's ensemble executors; each may submit one verdict per market
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match agent_executor.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankEmission<'info> {
+    #[account(
+        mut,
+        seeds = [INSIGHT_POOL_SEED],
+        bump = insight_pool.bump
+    )]
+    pub insight_pool: Account<'info, InsightPool>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeInsightRewards<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [INSIGHT_POOL_SEED],
+        bump = insight_pool.bump
+    )]
+    pub insight_pool: Account<'info, InsightPool>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), recipient_vault.owner.as_ref()],
+        bump = cred_stake.bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, recipient_vault.owner.as_ref()],
+        bump = recipient_vault.bump
+    )]
+    pub recipient_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [AGENT_EXECUTOR_SEED],
+        bump = agent_executor.bump
+    )]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match agent_executor.authority)
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCredStake<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = cred_stake.bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    /// Optional: the active Season, to record this settlement in the user's season stats
+    pub season: Option<Account<'info, Season>>,
+    
+    #[account(mut)]
+    pub season_stats: Option<Account<'info, SeasonStats>>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token mode: the market's Cred escrow token account
+    #[account(mut)]
+    pub market_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Optional: the referrer's vault, to pay their referral bonus once it is due
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, referrer_vault.owner.as_ref()],
+        bump = referrer_vault.bump
+    )]
+    pub referrer_vault: Option<Account<'info, ReputationVault>>,
+    
+    /// Token mode: the referrer's Cred token account
+    #[account(mut)]
+    pub referrer_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralBonus<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, referee_vault.owner.as_ref()],
+        bump = referee_vault.bump
+    )]
+    pub referee_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, referrer_vault.owner.as_ref()],
+        bump = referrer_vault.bump
+    )]
+    pub referrer_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the referrer's Cred token account
+    #[account(mut)]
+    pub referrer_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct BoostMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, booster.key().as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        init_if_needed,
+        payer = booster,
+        space = 8 + MarketBoost::INIT_SPACE,
+        seeds = [MARKET_BOOST_SEED, market.key().as_ref(), booster.key().as_ref()],
+        bump
+    )]
+    pub market_boost: Account<'info, MarketBoost>,
+    
+    #[account(mut)]
+    pub booster: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the booster's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepMarketPool<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [INSIGHT_POOL_SEED],
+        bump = insight_pool.bump
+    )]
+    pub insight_pool: Account<'info, InsightPool>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Anyone may crank an expired market; the reward goes to their vault
+    pub caller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, caller.key().as_ref()],
+        bump = caller_vault.bump
+    )]
+    pub caller_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the caller's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct RefundCredStake<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = cred_stake.bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token mode: the market's Cred escrow token account
+    #[account(mut)]
+    pub market_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct AuditMarket<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Token mode: the Cred mint
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the market's Cred escrow token account
+    pub market_escrow: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+#[instruction(metric: LeaderboardMetric)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [LEADERBOARD_SEED, &[metric as u8]],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitToLeaderboard<'info> {
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, &[leaderboard.metric as u8]],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    
+    #[account(
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSeason<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [SEASON_SEED, &1u32.to_le_bytes()],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// The authority signer (must match protocol_config.authority)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct JoinSeason<'info> {
+    #[account(
+        seeds = [SEASON_SEED, &season.number.to_le_bytes()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SeasonStats::INIT_SPACE,
+        seeds = [SEASON_STATS_SEED, season.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump
+    )]
+    pub season_stats: Account<'info, SeasonStats>,
+    
+    #[account(
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitSeasonStats<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &season.number.to_le_bytes()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        seeds = [SEASON_STATS_SEED, season.key().as_ref(), season_stats.owner.as_ref()],
+        bump = season_stats.bump
+    )]
+    pub season_stats: Account<'info, SeasonStats>,
+}
+
+#[derive(Accounts)]
+pub struct EndSeason<'info> {
+    #[account(
+        mut,
+        seeds = [SEASON_SEED, &protocol_config.current_season.to_le_bytes()],
+        bump = current_season.bump
+    )]
+    pub current_season: Account<'info, Season>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [SEASON_SEED, &(protocol_config.current_season + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_season: Account<'info, Season>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EarnCred<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
+    )]
+    pub reputation_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        seeds = [AGENT_EXECUTOR_SEED],
+        bump = agent_executor.bump
+    )]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match agent_executor.authority)
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct TransferCred<'info> {
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, sender.key().as_ref()],
+        bump = sender_vault.bump
+    )]
+    pub sender_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, recipient_vault.owner.as_ref()],
+        bump = recipient_vault.bump
+    )]
+    pub recipient_vault: Account<'info, ReputationVault>,
+    
+    pub sender: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the sender's Cred token account
+    #[account(mut)]
+    pub sender_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token mode: the recipient's Cred token account
+    #[account(mut)]
+    pub recipient_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+#[instruction(evidence_index: u8)]
+pub struct TipEvidence<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [EVIDENCE_SEED, market.key().as_ref(), &[evidence_index]],
+        bump = evidence.bump
+    )]
+    pub evidence: Account<'info, Evidence>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, sender.key().as_ref()],
+        bump = sender_vault.bump
+    )]
+    pub sender_vault: Account<'info, ReputationVault>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, evidence.submitter.as_ref()],
+        bump = submitter_vault.bump
+    )]
+    pub submitter_vault: Account<'info, ReputationVault>,
+    
+    pub sender: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the sender's Cred token account
+    #[account(mut)]
+    pub sender_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// Token mode: the recipient's Cred token account
+    #[account(mut)]
+    pub recipient_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-// ============================================================================
-// ACCOUNTS
-// ============================================================================
+#[derive(Accounts)]
+pub struct DisputeMarket<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Optional: the disputer's identity attestation, when the protocol requires one
+    #[account(
+        seeds = [ATTESTATION_SEED, attestation.attester.as_ref(), disputer.key().as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+}
 
 #[derive(Accounts)]
-pub struct InitializeInsightPool<'info> {
+pub struct SettleDispute<'info> {
+    #[account(mut, has_one = agent_executor @ ErrorCode::WrongExecutor)]
+    pub market: Account<'info, Market>,
+    
     #[account(
-        init,
-        payer = authority,
-        space = 8 + InsightPool::INIT_SPACE,
-        seeds = [INSIGHT_POOL_SEED],
-        bump
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
     )]
-    pub insight_pool: Account<'info, InsightPool>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
     
+    /// The executor that resolved the market
     #[account(mut)]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
+    /// The authority signer (must match protocol_config.authority)
     pub authority: Signer<'info>,
     
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// The disputer's vault, to pay their share; required when a single executor's
+    /// resolution is overturned
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, disputer_vault.owner.as_ref()],
+        bump = disputer_vault.bump
+    )]
+    pub disputer_vault: Option<Account<'info, ReputationVault>>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the disputer's Cred token account
+    #[account(mut)]
+    pub disputer_cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeAgentExecutor<'info> {
+pub struct PostExecutorBond<'info> {
+    #[account(mut)]
+    pub agent_executor: Account<'info, AgentExecutor>,
+    
     #[account(
-        init,
-        payer = authority,
-        space = 8 + AgentExecutor::INIT_SPACE,
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, depositor.key().as_ref()],
+        bump = depositor_vault.bump
     )]
-    pub agent_executor: Account<'info, AgentExecutor>,
+    pub depositor_vault: Account<'info, ReputationVault>,
+    
+    pub depositor: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
     
+    /// Token mode: the Cred mint
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     
-    pub system_program: Program<'info, System>,
+    /// Token mode: the depositor's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeReputationVault<'info> {
+pub struct ClaimSlashCompensation<'info> {
+    pub market: Account<'info, Market>,
+    
     #[account(
-        init,
-        payer = payer,
-        space = 8 + ReputationVault::INIT_SPACE,
-        seeds = [REPUTATION_VAULT_SEED, owner.key().as_ref()],
-        bump
+        mut,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = cred_stake.bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
     )]
     pub reputation_vault: Account<'info, ReputationVault>,
     
-    /// CHECK: The owner of this vault
-    pub owner: AccountInfo<'info>,
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     
-    pub system_program: Program<'info, System>,
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-#[instruction(tweet_url: String, market_id: String)]
-pub struct InitializeMarket<'info> {
+pub struct InitializeSponsorEscrow<'info> {
+    pub market: Account<'info, Market>,
+    
     #[account(
         init,
-        payer = creator,
-        space = 8 + Market::INIT_SPACE,
-        seeds = [MARKET_SEED, market_id.as_bytes()],
+        payer = sponsor,
+        space = 8 + SponsorEscrow::INIT_SPACE,
+        seeds = [SPONSOR_ESCROW_SEED, market.key().as_ref()],
         bump
     )]
-    pub market: Account<'info, Market>,
+    pub sponsor_escrow: Account<'info, SponsorEscrow>,
     
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    /// The market creator, approving the sponsor of a market that reserved none
+    pub creator: Option<Signer<'info>>,
+    
+    /// SPL escrow: the sponsored token's mint
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// SPL escrow: the vault holding the sponsored tokens
     #[account(
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump = agent_executor.bump
+        init,
+        payer = sponsor,
+        seeds = [SPONSOR_VAULT_SEED, sponsor_escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = sponsor_escrow,
+        token::token_program = token_program,
     )]
-    pub agent_executor: Account<'info, AgentExecutor>,
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
+    /// SPL escrow: the sponsor's token account the deposit is taken from
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub sponsor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct StakeCred<'info> {
-    #[account(mut)]
+pub struct DepositSponsorEscrow<'info> {
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        seeds = [REPUTATION_VAULT_SEED, user.key().as_ref()],
-        bump = reputation_vault.bump
+        seeds = [SPONSOR_ESCROW_SEED, market.key().as_ref()],
+        bump = sponsor_escrow.bump,
+        has_one = sponsor @ ErrorCode::UnauthorizedSponsor
     )]
-    pub reputation_vault: Account<'info, ReputationVault>,
+    pub sponsor_escrow: Account<'info, SponsorEscrow>,
+    
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
     
+    /// SPL escrow: the sponsored token's mint
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// SPL escrow: the vault holding the sponsored tokens
     #[account(
-        init,
-        payer = user,
-        space = 8 + CredStake::INIT_SPACE,
-        seeds = [CRED_STAKE_SEED, market.key().as_ref(), user.key().as_ref()],
+        mut,
+        seeds = [SPONSOR_VAULT_SEED, sponsor_escrow.key().as_ref()],
         bump
     )]
-    pub cred_stake: Account<'info, CredStake>,
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
+    /// SPL escrow: the sponsor's token account the deposit is taken from
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub sponsor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SubmitEvidence<'info> {
+pub struct ClaimSponsorReward<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [SPONSOR_ESCROW_SEED, market.key().as_ref()],
+        bump = sponsor_escrow.bump
+    )]
+    pub sponsor_escrow: Account<'info, SponsorEscrow>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_STAKE_SEED, market.key().as_ref(), staker.key().as_ref()],
+        bump = cred_stake.bump
+    )]
+    pub cred_stake: Account<'info, CredStake>,
+    
+    /// CHECK: The stake's owner, paid SOL rewards directly; bound by the cred_stake seeds
+    #[account(mut)]
+    pub staker: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// SPL escrow: the sponsored token's mint
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// SPL escrow: the vault holding the sponsored tokens
+    #[account(
+        mut,
+        seeds = [SPONSOR_VAULT_SEED, sponsor_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// SPL escrow: the staker's token account for the sponsored mint
     #[account(mut)]
+    pub staker_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct RefundSponsorEscrow<'info> {
     pub market: Account<'info, Market>,
     
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [SPONSOR_ESCROW_SEED, market.key().as_ref()],
+        bump = sponsor_escrow.bump,
+        has_one = sponsor @ ErrorCode::UnauthorizedSponsor
+    )]
+    pub sponsor_escrow: Account<'info, SponsorEscrow>,
+    
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub sponsor: Signer<'info>,
     
-    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// SPL escrow: the sponsored token's mint
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// SPL escrow: the vault holding the sponsored tokens, closed with the escrow
+    #[account(
+        mut,
+        seeds = [SPONSOR_VAULT_SEED, sponsor_escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    /// SPL escrow: the sponsor's token account the refund is paid to
+    #[account(mut)]
+    pub sponsor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveMarket<'info> {
+pub struct StakeOnOracle<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump = agent_executor.bump
+        seeds = [REPUTATION_VAULT_SEED, user.key().as_ref()],
+        bump = reputation_vault.bump
     )]
-    pub agent_executor: Account<'info, AgentExecutor>,
+    pub reputation_vault: Account<'info, ReputationVault>,
     
-    /// The authority signer (must match agent_executor.authority)
-    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + OracleStake::INIT_SPACE,
+        seeds = [ORACLE_STAKE_SEED, market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct DistributeInsightRewards<'info> {
+pub struct ResolveOracleStake<'info> {
+    #[account(has_one = agent_executor @ ErrorCode::WrongExecutor)]
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        seeds = [INSIGHT_POOL_SEED],
-        bump = insight_pool.bump
+        seeds = [ORACLE_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = oracle_stake.bump
     )]
-    pub insight_pool: Account<'info, InsightPool>,
+    pub oracle_stake: Account<'info, OracleStake>,
     
     #[account(
-        seeds = [CRED_STAKE_SEED, market.key().as_ref(), recipient_vault.owner.as_ref()],
-        bump = cred_stake.bump
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
+        bump = reputation_vault.bump
     )]
-    pub cred_stake: Account<'info, CredStake>,
+    pub reputation_vault: Account<'info, ReputationVault>,
     
     #[account(
-        mut,
-        seeds = [REPUTATION_VAULT_SEED, recipient_vault.owner.as_ref()],
-        bump = recipient_vault.bump
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
     )]
-    pub recipient_vault: Account<'info, ReputationVault>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
     
     #[account(
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump = agent_executor.bump
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
     )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// The executor that resolved the market, whose record prices the odds
     pub agent_executor: Account<'info, AgentExecutor>,
     
-    /// The authority signer (must match agent_executor.authority)
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct EarnCred<'info> {
     #[account(
         mut,
-        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
-        bump = reputation_vault.bump
+        seeds = [INSIGHT_POOL_SEED],
+        bump = insight_pool.bump
     )]
-    pub reputation_vault: Account<'info, ReputationVault>,
+    pub insight_pool: Account<'info, InsightPool>,
     
-    #[account(
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump = agent_executor.bump
-    )]
-    pub agent_executor: Account<'info, AgentExecutor>,
+    /// Optional: the active Season, to record this settlement in the user's season stats
+    pub season: Option<Account<'info, Season>>,
     
-    /// The authority signer (must match agent_executor.authority)
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct DisputeMarket<'info> {
     #[account(mut)]
-    pub market: Account<'info, Market>,
+    pub season_stats: Option<Account<'info, SeasonStats>>,
     
+    /// Token mode: the Cred mint
     #[account(mut)]
-    pub disputer: Signer<'info>,
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct StakeOnOracle<'info> {
+pub struct RefundOracleStake<'info> {
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        seeds = [REPUTATION_VAULT_SEED, user.key().as_ref()],
+        seeds = [ORACLE_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = oracle_stake.bump
+    )]
+    pub oracle_stake: Account<'info, OracleStake>,
+    
+    #[account(
+        mut,
+        seeds = [REPUTATION_VAULT_SEED, reputation_vault.owner.as_ref()],
         bump = reputation_vault.bump
     )]
     pub reputation_vault: Account<'info, ReputationVault>,
     
     #[account(
-        init,
-        payer = user,
-        space = 8 + OracleStake::INIT_SPACE,
-        seeds = [ORACLE_STAKE_SEED, market.key().as_ref(), user.key().as_ref()],
-        bump
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
     )]
-    pub oracle_stake: Account<'info, OracleStake>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
     
+    /// Token mode: the Cred mint
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
     
-    pub system_program: Program<'info, System>,
+    /// Token mode: the vault owner's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveOracleStake<'info> {
+pub struct RefundMarketBoost<'info> {
+    #[account(mut)]
     pub market: Account<'info, Market>,
     
     #[account(
         mut,
-        seeds = [ORACLE_STAKE_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
-        bump = oracle_stake.bump
+        seeds = [MARKET_BOOST_SEED, market.key().as_ref(), reputation_vault.owner.as_ref()],
+        bump = market_boost.bump
     )]
-    pub oracle_stake: Account<'info, OracleStake>,
+    pub market_boost: Account<'info, MarketBoost>,
     
     #[account(
         mut,
@@ -656,13 +5294,27 @@ pub struct ResolveOracleStake<'info> {
     pub reputation_vault: Account<'info, ReputationVault>,
     
     #[account(
-        seeds = [AGENT_EXECUTOR_SEED],
-        bump = agent_executor.bump
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
     )]
-    pub agent_executor: Account<'info, AgentExecutor>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
     
-    /// The authority signer (must match agent_executor.authority)
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [CRED_LEDGER_SEED],
+        bump = cred_ledger.bump
+    )]
+    pub cred_ledger: Account<'info, CredLedger>,
+    
+    /// Token mode: the Cred mint
+    #[account(mut)]
+    pub cred_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    
+    /// Token mode: the booster's Cred token account
+    #[account(mut)]
+    pub cred_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 // ============================================================================
@@ -681,11 +5333,41 @@ pub struct Market {
     pub status: MarketStatus,
     pub outcome: Option<u8>,
     pub ipfs_transcript_hash: [u8; 32],
-    pub insight_pool_amount: u64,
+    pub confidence_bps: u16,
+    #[max_len(32)]
+    pub model_id: String,
+    pub researcher_hash: [u8; 32],
+    pub judge_hash: [u8; 32],
+    pub settlement_bps: u16, // Share of the pool paid to YES stakers (10000 = YES, 0 = NO)
+    pub resolved_at: i64,
+    pub dispute_status: DisputeStatus,
+    pub insight_pool_amount: u64, // Global InsightPool rewards distributed on this market
+    pub market_pool: u64, // Cred boosted into this market's own pot for its winners
+    pub market_pool_paid_out: u64,
+    pub market_pool_swept: bool, // Remainder returned to the InsightPool
+    pub market_pool_swept_amount: u64,
+    pub market_pool_refunded: u64, // Boosts returned to their boosters on a voided market
     pub agent_executor: Pubkey,
+    pub sponsor: Option<Pubkey>, // Only this key may fund the sponsor escrow; None = anyone the creator approves
     pub evidence_count: u8,
     pub total_yes_stake: u64,
     pub total_no_stake: u64,
+    pub oracle_for_stake: u64, // Oracle stakes betting the resolution stands
+    pub oracle_against_stake: u64, // Oracle stakes betting it is overturned
+    pub disputer: Pubkey, // Default until the market is disputed
+    pub overturned_outcome: Option<u8>, // Original verdict, once a dispute replaced it
+    pub slash_compensation: u64, // Executor slash set aside for stakers wronged by an overturned resolution
+    pub ensemble_quorum: u8, // Agreeing verdicts required to resolve; 0 = single executor
+    pub ensemble_size: u8,
+    #[max_len(MAX_ENSEMBLE_SIZE)]
+    pub ensemble_executors: Vec<Pubkey>, // AgentExecutor accounts allowed to submit verdicts
+    pub verdict_nonce: u64, // Signed verdicts must carry a higher nonce
+    pub verdict_count: u8,
+    pub verdict_tallies: [u8; 3], // Verdicts per outcome: NO, YES, INVALID
+    pub verdict_confidence_total: [u32; 3],
+    pub total_paid_out: u64, // Pari-mutuel payouts claimed from the pool
+    pub total_refunded: u64, // At-par refunds on an Invalid market
+    pub settled_stake: u64, // Stake principal already claimed or refunded
     pub bump: u8,
 }
 
@@ -697,6 +5379,160 @@ pub struct ReputationVault {
     pub total_earned: u64,
     pub total_staked: u64,
     pub participation_count: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub settled_count: u64,
+    pub settled_stake: u64, // Stake principal settled, gates the referral bonus
+    pub brier_score_total: u64, // Sum of per-settlement Brier scores in bps; divide by settled_count
+    pub transfer_day: i64, // Unix day of the current transfer window
+    pub transferred_today: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_count: u32, // Referees this vault has brought in
+    pub referral_bonus_paid: bool, // Whether this vault's referrer has been paid for it
+    pub locked_grant: u64, // Initial grant still waiting to unlock
+    pub last_faucet_claim: i64,
+    pub faucet_balance: u64, // Unspent faucet Cred, which cannot be transferred
+    pub earn_day: i64, // Unix day of earned_today_by_method
+    pub earned_today_by_method: [u64; EARN_METHOD_COUNT], // earn_cred grants today, by EarnMethod
+    pub earned_by_method: [u64; EARN_METHOD_COUNT], // Lifetime Cred earned, by EarnMethod
+    pub token_backed: bool, // Token mode: the balance is held as Cred tokens
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Faucet {
+    pub epoch: u64,
+    pub epoch_start: i64,
+    pub distributed_in_epoch: u64,
+    pub total_distributed: u64,
+    pub claims: u64,
+    pub bump: u8,
+}
+
+impl ReputationVault {
+    /// Add to the vault's per-EarnMethod audit trail
+    pub fn record_earned(&mut self, method: EarnMethod, amount: u64) -> Result<()> {
+        let earned = &mut self.earned_by_method[method as usize];
+        *earned = earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Cred that may leave the vault through transfers and tips
+    pub fn transferable_balance(&self) -> u64 {
+        self.cred_balance.saturating_sub(self.faucet_balance)
+    }
+
+    /// Stakes, boosts and bonds draw on faucet Cred before the rest of the balance
+    pub fn spend_faucet_first(&mut self, amount: u64) {
+        self.faucet_balance = self.faucet_balance.saturating_sub(amount);
+    }
+}
+
+/// Global Cred supply accounting. Mints and burns change the supply; stakes,
+/// payouts and refunds move Cred in and out of market escrows.
+#[account]
+#[derive(InitSpace)]
+pub struct CredLedger {
+    pub total_minted: u64,
+    pub total_burned: u64, // Oracle stakes
+    pub total_staked: u64,
+    pub total_paid_out: u64,
+    pub total_refunded: u64,
+    pub bump: u8,
+}
+
+impl CredLedger {
+    /// Cred in existence, in vaults or market escrows
+    pub fn outstanding(&self) -> Option<u64> {
+        self.total_minted.checked_sub(self.total_burned)
+    }
+
+    /// Cred held in market escrows
+    pub fn escrowed(&self) -> Option<u64> {
+        self.total_staked
+            .checked_sub(self.total_paid_out)?
+            .checked_sub(self.total_refunded)
+    }
+
+    pub fn record_mint(&mut self, amount: u64) -> Result<()> {
+        self.total_minted = self.total_minted.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_burn(&mut self, amount: u64) -> Result<()> {
+        self.total_burned = self.total_burned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_stake(&mut self, amount: u64) -> Result<()> {
+        self.total_staked = self.total_staked.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        self.total_paid_out = self.total_paid_out.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.total_refunded = self.total_refunded.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+impl Faucet {
+    /// Advance to the epoch containing `now`, resetting the epoch's distributed amount
+    pub fn roll_epoch(&mut self, now: i64, epoch_duration: i64) {
+        let elapsed = now.saturating_sub(self.epoch_start) / epoch_duration;
+        if elapsed > 0 {
+            self.epoch = self.epoch.saturating_add(elapsed as u64);
+            self.epoch_start = self.epoch_start.saturating_add(elapsed.saturating_mul(epoch_duration));
+            self.distributed_in_epoch = 0;
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub subject: Pubkey,
+    pub attester: Pubkey,
+    pub attestation_type: AttestationType,
+    pub handle_hash: [u8; 32], // Hash of the verified handle, e.g. a Twitter username
+    pub issued_at: i64,
+    pub expires_at: i64, // 0 = never expires
+    pub rewarded: bool, // IdentityVerification Cred already paid
+    pub bump: u8,
+}
+
+impl Attestation {
+    /// Whether the attestation has not expired
+    pub fn is_valid(&self, now: i64) -> bool {
+        self.expires_at == 0 || self.expires_at > now
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Attester {
+    pub attester: Pubkey,
+    pub active: bool,
+    pub attestations_issued: u64,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Evidence {
+    pub market: Pubkey,
+    pub submitter: Pubkey,
+    pub index: u8, // 1-based, matches EvidenceSubmitted.evidence_index
+    #[max_len(MAX_IPFS_CID_LEN)]
+    pub ipfs_cid: String,
+    pub tips_received: u64,
+    pub submitted_at: i64,
     pub bump: u8,
 }
 
@@ -707,14 +5543,78 @@ pub struct InsightPool {
     pub distributions_count: u64,
     pub last_distribution: i64,
     pub authority: Pubkey,
+    pub epoch: u64, // 0 until the first crank_emission
+    pub epoch_start: i64,
+    pub epoch_budget: u64, // Cred emitted for the current epoch
+    pub remaining_budget: u64, // Cred still distributable this epoch
+    pub total_emitted: u64,
+    pub house_edge_collected: u64, // Oracle stake house edge added to epoch budgets
+    pub market_pools_swept: u64, // Unclaimed market pool Cred added to epoch budgets
     pub bump: u8,
 }
 
+impl InsightPool {
+    /// Move to the epoch containing `now`. The first call starts epoch 1 at `now`;
+    /// later calls only advance once the current epoch has run its full duration.
+    /// Returns whether a new epoch was opened.
+    pub fn advance_epoch(&mut self, now: i64, epoch_duration: i64) -> Result<bool> {
+        if self.epoch == 0 {
+            self.epoch = 1;
+            self.epoch_start = now;
+            return Ok(true);
+        }
+        let elapsed = now.saturating_sub(self.epoch_start) / epoch_duration;
+        if elapsed <= 0 {
+            return Ok(false);
+        }
+        self.epoch = self.epoch.checked_add(elapsed as u64).ok_or(ErrorCode::Overflow)?;
+        self.epoch_start = self
+            .epoch_start
+            .checked_add(elapsed.checked_mul(epoch_duration).ok_or(ErrorCode::Overflow)?)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(true)
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct AgentExecutor {
     pub authority: Pubkey,
     pub markets_resolved: u64,
+    pub resolutions_upheld: u64, // Resolutions standing: undisputed or upheld on dispute
+    pub resolutions_overturned: u64,
+    pub bond: u64, // Slashable Cred; resolving requires at least executor_min_bond
+    pub total_slashed: u64,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String, // Describes the resolver, e.g. its model and methodology
+    pub active: bool, // Inactive executors cannot be chosen for new markets or resolve
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl AgentExecutor {
+    /// Chance of a resolution being upheld, in basis points, from the executor's record.
+    /// Laplace-smoothed so a new executor starts at 50% and never reaches 0% or 100%.
+    pub fn upheld_bps(&self) -> Result<u16> {
+        let upheld = (self.resolutions_upheld as u128).checked_add(1).ok_or(ErrorCode::Overflow)?;
+        let total = upheld
+            .checked_add(self.resolutions_overturned as u128)
+            .and_then(|total| total.checked_add(1))
+            .ok_or(ErrorCode::Overflow)?;
+        let bps = upheld * BPS_DENOMINATOR as u128 / total;
+        Ok((bps as u16).clamp(1, BPS_DENOMINATOR - 1))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ExecutorVerdict {
+    pub market: Pubkey,
+    pub executor: Pubkey,
+    pub outcome: u8,
+    pub ipfs_transcript_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub submitted_at: i64,
     pub bump: u8,
 }
 
@@ -726,6 +5626,38 @@ pub struct CredStake {
     pub amount: u64,
     pub direction: bool,
     pub timestamp: i64,
+    pub claimed: bool,
+    pub compensated: bool, // Slash compensation claimed
+    pub sponsor_claimed: bool, // Sponsor escrow share claimed
+    pub insight_distributed: bool, // InsightPool reward paid
+    pub bump: u8,
+}
+
+/// One booster's total contribution to a market's pool
+#[account]
+#[derive(InitSpace)]
+pub struct MarketBoost {
+    pub market: Pubkey,
+    pub booster: Pubkey,
+    pub amount: u64,
+    pub refunded: bool, // Returned because the market was voided
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SponsorEscrow {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub mint: Option<Pubkey>, // SPL token held in the escrow vault; None = SOL held by this account
+    #[max_len(MAX_SPONSOR_NAME_LEN)]
+    pub sponsor_name: String,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String,
+    pub amount: u64, // Total deposited
+    pub distributed: u64, // Paid out to winning stakers
+    pub is_released: bool, // Remainder refunded to the sponsor
+    pub created_at: i64,
     pub bump: u8,
 }
 
@@ -735,11 +5667,117 @@ pub struct OracleStake {
     pub user: Pubkey,
     pub market: Pubkey,
     pub amount: u64,
+    pub backs_oracle: bool, // false = short on the resolution
     pub timestamp: i64,
     pub claimed: bool,
     pub bump: u8,
 }
 
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolConfig {
+    pub authority: Pubkey,
+    pub invalid_oracle_policy: InvalidOraclePolicy,
+    pub current_season: u32, // 0 until the first Season is opened
+    pub cred_mint: Option<Pubkey>, // Set once Cred runs in SPL token mode
+    pub cred_mint_bump: u8,
+    pub cred_non_transferable: bool,
+    pub daily_transfer_limit: u64, // Max Cred a vault may send per day
+    pub referral_bonus: u64,
+    pub referral_min_stake: u64, // Referee stake that must settle before their referrer is paid
+    pub max_referrals_per_referrer: u32,
+    pub unattested_grant: u64, // Initial Cred paid up front to vaults without an attestation
+    pub grant_tranche: u64, // Max locked grant released per settled stake, capped by the stake amount
+    pub identity_reward: u64,
+    pub require_attestation_for_markets: bool,
+    pub require_attestation_for_disputes: bool,
+    pub faucet_amount: u64, // Cred per faucet claim; 0 disables the faucet
+    pub faucet_cooldown: i64,
+    pub faucet_epoch_duration: i64,
+    pub faucet_epoch_budget: u64,
+    pub faucet_balance_threshold: u64, // Vaults at or above this balance cannot claim
+    pub earn_method_daily_caps: [u64; EARN_METHOD_COUNT], // Per-vault earn_cred limit per day, by EarnMethod
+    pub daily_mint_ceiling: u64, // earn_cred and crank reward limit per day across all vaults
+    pub mint_day: i64,
+    pub minted_today: u64,
+    pub emission_per_epoch: u64, // InsightPool budget for its first epoch
+    pub emission_decay_bps: u16, // Budget reduction applied each following epoch
+    pub emission_epoch_duration: i64,
+    pub dispute_window: i64, // Seconds after resolution during which a market can be disputed
+    pub oracle_house_edge_bps: u16, // Share of oracle stake winnings routed to the InsightPool
+    pub executor_min_bond: u64,
+    pub executor_slash_bps: u16, // Share of the executor bond slashed per overturned resolution
+    pub disputer_slash_share_bps: u16,
+    pub market_max_age: i64, // Seconds after creation before an unresolved market can be voided
+    pub expiry_crank_reward: u64, // Cred minted to the caller of expire_market
+    pub market_pool_claim_window: i64, // Seconds after resolution before a market pool can be swept
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    /// Count discretionary Cred minted on unix day `today` against the global daily mint ceiling
+    pub fn record_daily_mint(&mut self, amount: u64, today: i64) -> Result<()> {
+        if self.mint_day != today {
+            self.mint_day = today;
+            self.minted_today = 0;
+        }
+        let minted_today = self.minted_today.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        require!(minted_today <= self.daily_mint_ceiling, ErrorCode::DailyMintCeilingExceeded);
+        self.minted_today = minted_today;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub metric: LeaderboardMetric,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub entries: Vec<LeaderboardEntry>, // Sorted by score, highest first
+    pub last_updated: i64,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Zero-based rank of `owner`, if they are on the board
+    pub fn rank_of(&self, owner: &Pubkey) -> Option<usize> {
+        rank_in(&self.entries, owner)
+    }
+
+    /// Insert or update `owner` with `score`, keeping the top `LEADERBOARD_SIZE`
+    pub fn submit(&mut self, owner: Pubkey, score: u64, timestamp: i64) {
+        insert_ranked(&mut self.entries, owner, score);
+        self.last_updated = timestamp;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub number: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub duration: i64,
+    pub status: SeasonStatus,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub leaderboard: Vec<LeaderboardEntry>, // Ranked by net Cred earned; frozen once the season ends
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonStats {
+    pub season: Pubkey,
+    pub owner: Pubkey,
+    pub cred_earned: u64,
+    pub cred_staked: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub settled_count: u64,
+    pub brier_score_total: u64,
+    pub bump: u8,
+}
+
 // ============================================================================
 // ENUMS
 // ============================================================================
@@ -749,6 +5787,51 @@ pub enum MarketStatus {
     Open,
     Resolved,
     Disputed,
+    Voided, // Expired without a resolution; every stake is refundable
+}
+
+/// Outcome of an ensemble market's verdicts so far
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EnsembleDecision {
+    Pending,
+    Quorum(u8),
+    Split, // No outcome can reach the quorum; escalated to dispute
+}
+
+/// Where a market's resolution stands in the dispute flow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum DisputeStatus {
+    None,
+    Pending,
+    Upheld,
+    Overturned,
+}
+
+/// What an attester verified about a user
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AttestationType {
+    TwitterHandle,
+    ProofOfPersonhood,
+}
+
+/// How oracle stakers are settled when a market resolves as Invalid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum InvalidOraclePolicy {
+    StakersWin,
+    StakersLose,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SeasonStatus {
+    Active,
+    Ended,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LeaderboardMetric {
+    NetCredEarned,
+    Accuracy,
+    Calibration,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
@@ -759,6 +5842,79 @@ pub enum EarnMethod {
     Referral,
     IdentityVerification,
     CommunityContribution,
+    Faucet,
+}
+
+// ============================================================================
+// STRUCTS
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub owner: Pubkey,
+    pub score: u64,
+}
+
+/// A verdict to record on a market, however it was reached
+pub struct Resolution {
+    pub outcome: u8,
+    pub ipfs_transcript_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub model_id: String,
+    pub researcher_hash: [u8; 32],
+    pub judge_hash: [u8; 32],
+    pub settlement_bps: Option<u16>,
+}
+
+// ============================================================================
+// INSTRUCTION ARGS
+// ============================================================================
+
+/// A verdict signed off-chain by an executor's key, for resolve_market_signed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignedVerdict {
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub ipfs_transcript_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub model_id: String,
+    pub researcher_hash: [u8; 32],
+    pub judge_hash: [u8; 32],
+    pub settlement_bps: Option<u16>,
+    pub expiry: i64, // Unix timestamp after which the verdict can no longer be submitted
+    pub nonce: u64, // Must exceed the market's verdict_nonce
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProtocolConfigUpdate {
+    pub invalid_oracle_policy: Option<InvalidOraclePolicy>,
+    pub daily_transfer_limit: Option<u64>,
+    pub referral_bonus: Option<u64>,
+    pub referral_min_stake: Option<u64>,
+    pub max_referrals_per_referrer: Option<u32>,
+    pub unattested_grant: Option<u64>,
+    pub grant_tranche: Option<u64>,
+    pub identity_reward: Option<u64>,
+    pub require_attestation_for_markets: Option<bool>,
+    pub require_attestation_for_disputes: Option<bool>,
+    pub faucet_amount: Option<u64>,
+    pub faucet_cooldown: Option<i64>,
+    pub faucet_epoch_duration: Option<i64>,
+    pub faucet_epoch_budget: Option<u64>,
+    pub faucet_balance_threshold: Option<u64>,
+    pub earn_method_daily_caps: Option<[u64; EARN_METHOD_COUNT]>,
+    pub daily_mint_ceiling: Option<u64>,
+    pub emission_per_epoch: Option<u64>,
+    pub emission_decay_bps: Option<u16>,
+    pub emission_epoch_duration: Option<i64>,
+    pub dispute_window: Option<i64>,
+    pub oracle_house_edge_bps: Option<u16>,
+    pub executor_min_bond: Option<u64>,
+    pub executor_slash_bps: Option<u16>,
+    pub disputer_slash_share_bps: Option<u16>,
+    pub market_max_age: Option<i64>,
+    pub expiry_crank_reward: Option<u64>,
+    pub market_pool_claim_window: Option<i64>,
 }
 
 // ============================================================================
@@ -797,10 +5953,66 @@ pub struct MarketResolved {
     pub market: Pubkey,
     pub outcome: u8,
     pub ipfs_transcript_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub model_id: String,
+    pub researcher_hash: [u8; 32],
+    pub judge_hash: [u8; 32],
+    pub settlement_bps: u16,
     pub resolver: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MarketPoolBoosted {
+    pub market: Pubkey,
+    pub booster: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+    pub by_creator: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketPoolSwept {
+    pub market: Pubkey,
+    pub amount: u64, // Returned to the InsightPool's current budget
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketExpired {
+    pub market: Pubkey,
+    pub caller: Pubkey,
+    pub crank_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignedVerdictRelayed {
+    pub market: Pubkey,
+    pub executor: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerdictSubmitted {
+    pub market: Pubkey,
+    pub executor: Pubkey,
+    pub outcome: u8,
+    pub ipfs_transcript_hash: [u8; 32],
+    pub confidence_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EnsembleSplit {
+    pub market: Pubkey,
+    pub verdict_tallies: [u8; 3],
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct CredDistributed {
     pub market: Pubkey,
@@ -809,6 +6021,86 @@ pub struct CredDistributed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct EmissionCranked {
+    pub epoch: u64,
+    pub budget: u64,
+    pub epoch_start: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredStakeClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub direction: bool,
+    pub payout: u64,
+    pub pool_share: u64, // From the market's own pool, on top of the pari-mutuel payout
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationUpdated {
+    pub user: Pubkey,
+    pub wins: u64,
+    pub losses: u64,
+    pub settled_count: u64,
+    pub brier_score_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LeaderboardUpdated {
+    pub leaderboard: Pubkey,
+    pub metric: LeaderboardMetric,
+    pub owner: Pubkey,
+    pub score: u64,
+    pub rank: Option<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonStarted {
+    pub number: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct SeasonEnded {
+    pub season: Pubkey,
+    pub number: u32,
+    pub winners: Vec<LeaderboardEntry>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredStakeRefunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketAudited {
+    pub market: Pubkey,
+    pub total_staked: u64,
+    pub total_paid_out: u64,
+    pub total_refunded: u64,
+    pub insight_pool_amount: u64,
+    pub escrowed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultMigratedToToken {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct CredEarned {
     pub user: Pubkey,
@@ -817,6 +6109,57 @@ pub struct CredEarned {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AttestationIssued {
+    pub subject: Pubkey,
+    pub attester: Pubkey,
+    pub attestation_type: AttestationType,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationRenewed {
+    pub subject: Pubkey,
+    pub attester: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub subject: Pubkey,
+    pub attester: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRecorded {
+    pub referrer: Pubkey,
+    pub referee: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CredTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub memo: Option<String>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EvidenceTipped {
+    pub market: Pubkey,
+    pub evidence_index: u8,
+    pub tipper: Pubkey,
+    pub submitter: Pubkey,
+    pub amount: u64,
+    pub memo: Option<String>,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ProofNFTMintRequested {
     pub market: Pubkey,
@@ -832,11 +6175,83 @@ pub struct MarketDisputed {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ExecutorRegistered {
+    pub executor: Pubkey,
+    pub authority: Pubkey,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExecutorBondPosted {
+    pub executor: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExecutorSlashed {
+    pub executor: Pubkey,
+    pub market: Pubkey,
+    pub amount: u64,
+    pub disputer_share: u64,
+    pub staker_share: u64, // Claimable by stakers via claim_slash_compensation
+    pub remaining_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlashCompensationClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorEscrowFunded {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorRewardClaimed {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorEscrowRefunded {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeSettled {
+    pub market: Pubkey,
+    pub upheld: bool,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct OracleStaked {
     pub market: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
+    pub backs_oracle: bool,
     pub timestamp: i64,
 }
 
@@ -845,11 +6260,31 @@ pub struct OracleStakeResolved {
     pub market: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
+    pub backs_oracle: bool,
     pub reward: u64,
+    pub house_fee: u64, // Withheld from the winnings and added to the InsightPool budget
+    pub withheld: u64, // Winnings held back for a low-confidence verdict, also added to the budget
     pub won: bool,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct OracleStakeRefunded {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub backs_oracle: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MarketBoostRefunded {
+    pub market: Pubkey,
+    pub booster: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 // ============================================================================
 // ERRORS
 // ============================================================================
@@ -877,7 +6312,7 @@ pub enum ErrorCode {
     #[msg("Invalid amount specified")]
     InvalidAmount,
     
-    #[msg("Invalid outcome value (must be 0 or 1)")]
+    #[msg("Invalid outcome value (must be 0, 1 or 2)")]
     InvalidOutcome,
     
     #[msg("Unauthorized resolver - only AgentExecutor authority can resolve")]
@@ -897,4 +6332,253 @@ pub enum ErrorCode {
     
     #[msg("Oracle stake has already been claimed")]
     OracleStakeAlreadyClaimed,
+    
+    #[msg("Confidence must be between 0 and 10000 basis points")]
+    InvalidConfidence,
+    
+    #[msg("Model ID exceeds maximum length of 32 characters")]
+    ModelIdTooLong,
+    
+    #[msg("Market was resolved as Invalid - rewards are not distributed")]
+    MarketInvalid,
+    
+    #[msg("Market was not resolved as Invalid")]
+    MarketNotInvalid,
+    
+    #[msg("Cred stake has already been claimed")]
+    StakeAlreadyClaimed,
+    
+    #[msg("Unauthorized - only the ProtocolConfig authority can update settings")]
+    UnauthorizedConfigAuthority,
+    
+    #[msg("Settlement must be between 0 and 10000 basis points and cannot be used with an Invalid outcome")]
+    InvalidSettlement,
+    
+    #[msg("Season duration must be positive")]
+    InvalidSeasonDuration,
+    
+    #[msg("A season has already been started")]
+    SeasonAlreadyStarted,
+    
+    #[msg("Season is not active")]
+    SeasonNotActive,
+    
+    #[msg("Season has not reached its end time")]
+    SeasonNotOver,
+    
+    #[msg("Season stats do not belong to this season or user")]
+    SeasonStatsMismatch,
+    
+    #[msg("Cred mint has already been initialized")]
+    CredMintAlreadyInitialized,
+    
+    #[msg("Cred token accounts are required while Cred runs in token mode")]
+    CredTokenAccountsRequired,
+    
+    #[msg("Account is not the configured Cred mint")]
+    InvalidCredMint,
+    
+    #[msg("Cred token account has the wrong mint or owner")]
+    InvalidCredTokenAccount,
+    
+    #[msg("Cannot transfer Cred to your own vault")]
+    SelfTransfer,
+    
+    #[msg("Memo exceeds maximum length")]
+    MemoTooLong,
+    
+    #[msg("Daily Cred transfer limit exceeded")]
+    DailyTransferLimitExceeded,
+    
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    
+    #[msg("The vault owner must sign to record a referrer")]
+    ReferralRequiresSignature,
+    
+    #[msg("Referrer has reached the maximum number of referrals")]
+    ReferralCapReached,
+    
+    #[msg("Referrer vault does not match the recorded referrer")]
+    ReferrerMismatch,
+    
+    #[msg("Referral bonus is not due")]
+    ReferralBonusNotDue,
+    
+    #[msg("Attestation expiry must be in the future")]
+    InvalidAttestationExpiry,
+    
+    #[msg("Attester is not active")]
+    AttesterInactive,
+    
+    #[msg("A valid identity attestation is required")]
+    AttestationRequired,
+    
+    #[msg("Identity verification reward already claimed")]
+    IdentityRewardAlreadyClaimed,
+    
+    #[msg("Invalid faucet configuration")]
+    InvalidFaucetConfig,
+    
+    #[msg("Faucet is disabled")]
+    FaucetDisabled,
+    
+    #[msg("Vault balance is too high to use the faucet")]
+    FaucetBalanceTooHigh,
+    
+    #[msg("Faucet cooldown has not elapsed")]
+    FaucetCooldown,
+    
+    #[msg("Faucet budget for this epoch is exhausted")]
+    FaucetBudgetExhausted,
+    
+    #[msg("Daily earn limit for this method reached")]
+    EarnMethodCapExceeded,
+    
+    #[msg("Global daily mint ceiling reached")]
+    DailyMintCeilingExceeded,
+    
+    #[msg("Cred accounting invariant violated")]
+    InvariantViolation,
+    
+    #[msg("Invalid emission configuration")]
+    InvalidEmissionConfig,
+    
+    #[msg("Current emission epoch has not ended")]
+    EmissionNotDue,
+    
+    #[msg("InsightPool budget for this epoch is exhausted")]
+    EmissionBudgetExhausted,
+    
+    #[msg("Dispute window must not be negative")]
+    InvalidDisputeWindow,
+    
+    #[msg("The dispute window for this market has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Market has already been disputed")]
+    AlreadyDisputed,
+    
+    #[msg("Market has no pending dispute")]
+    NoPendingDispute,
+    
+    #[msg("Market is not final - it is disputed or still within its dispute window")]
+    MarketNotFinal,
+    
+    #[msg("House edge cannot exceed 100%")]
+    InvalidHouseEdge,
+    
+    #[msg("Slash shares cannot exceed 100%")]
+    InvalidSlashConfig,
+    
+    #[msg("Executor bond is below the minimum required to resolve markets")]
+    ExecutorBondTooLow,
+    
+    #[msg("Disputer vault does not belong to the market's disputer")]
+    InvalidDisputerVault,
+    
+    #[msg("Stake is not owed compensation - the market was not overturned or the stake won")]
+    NotAffectedBySlash,
+    
+    #[msg("Slash compensation already claimed")]
+    CompensationAlreadyClaimed,
+    
+    #[msg("Metadata URI exceeds maximum length")]
+    MetadataUriTooLong,
+    
+    #[msg("Executor is not active")]
+    ExecutorInactive,
+    
+    #[msg("Executor is not the market's resolver")]
+    WrongExecutor,
+    
+    #[msg("Only the market creator can do this")]
+    UnauthorizedCreator,
+    
+    #[msg("Ensemble needs a quorum of at least 2, no larger than its size, and at most 7 executors, set before any verdict")]
+    InvalidEnsemble,
+    
+    #[msg("Market resolves by executor ensemble - submit a verdict instead")]
+    EnsembleMarket,
+    
+    #[msg("Market does not use an executor ensemble")]
+    NotEnsembleMarket,
+    
+    #[msg("Transaction has no Ed25519 signature check before this instruction")]
+    MissingSignatureVerification,
+    
+    #[msg("Signed verdict does not match the market or the executor's signature")]
+    InvalidSignedVerdict,
+    
+    #[msg("Signed verdict has expired")]
+    VerdictExpired,
+    
+    #[msg("Market max age must be positive")]
+    InvalidMarketMaxAge,
+    
+    #[msg("Market has not reached its maximum age")]
+    MarketNotExpired,
+    
+    #[msg("Market has not been voided")]
+    MarketNotVoided,
+    
+    #[msg("Sponsor name too long")]
+    SponsorNameTooLong,
+    
+    #[msg("Only the market's sponsor can do this")]
+    UnauthorizedSponsor,
+    
+    #[msg("Sponsor escrow token accounts are missing or do not match its mint")]
+    InvalidSponsorAccount,
+    
+    #[msg("Sponsor escrow has already been refunded")]
+    SponsorEscrowReleased,
+    
+    #[msg("Sponsor reward already claimed")]
+    SponsorRewardAlreadyClaimed,
+    
+    #[msg("Stake is not on a winning side")]
+    NotAWinningStake,
+    
+    #[msg("Sponsor escrow is only refundable for voided, invalid or winnerless markets, or after the claim window")]
+    SponsorEscrowNotRefundable,
+    
+    #[msg("Market pool claim window must not be negative")]
+    InvalidClaimWindow,
+    
+    #[msg("Market pool has already been swept")]
+    MarketPoolSwept,
+    
+    #[msg("Market pool claim window is still open")]
+    ClaimWindowOpen,
+    
+    #[msg("The disputer's vault is required to settle an overturned dispute")]
+    DisputerVaultRequired,
+    
+    #[msg("Overturning needs a corrected outcome that differs from the verdict; upholding takes none")]
+    InvalidCorrectedOutcome,
+    
+    #[msg("Executor is not part of this market's ensemble")]
+    ExecutorNotInEnsemble,
+    
+    #[msg("Market boost has already been refunded")]
+    BoostAlreadyRefunded,
+    
+    #[msg("Vault must be migrated to Cred tokens first")]
+    VaultNotTokenBacked,
+    
+    #[msg("Vault is already backed by Cred tokens")]
+    VaultAlreadyTokenBacked,
+    
+    #[msg("Faucet Cred cannot be transferred until it has been staked")]
+    FaucetCredNotTransferable,
+    
+    #[msg("InsightPool reward already distributed for this stake")]
+    InsightAlreadyDistributed,
+    
+    #[msg("An ensemble must be set before any Cred is staked")]
+    EnsembleAfterStakes,
+    
+    #[msg("Signed verdict nonce has been used or revoked")]
+    StaleVerdictNonce,
 }
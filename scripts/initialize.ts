@@ -63,13 +63,34 @@ async function main() {
         PROGRAM_ID
     );
 
+    const [protocolConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('protocol_config')],
+        PROGRAM_ID
+    );
+
+    const [faucetPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('faucet')],
+        PROGRAM_ID
+    );
+
+    const [credLedgerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('cred_ledger')],
+        PROGRAM_ID
+    );
+
     console.log('📍 InsightPool PDA:', insightPoolPda.toBase58());
     console.log('📍 AgentExecutor PDA:', agentExecutorPda.toBase58());
+    console.log('📍 ProtocolConfig PDA:', protocolConfigPda.toBase58());
+    console.log('📍 Faucet PDA:', faucetPda.toBase58());
+    console.log('📍 CredLedger PDA:', credLedgerPda.toBase58());
     console.log('');
 
     // Check if already initialized
     const insightPoolAccount = await connection.getAccountInfo(insightPoolPda);
     const agentExecutorAccount = await connection.getAccountInfo(agentExecutorPda);
+    const protocolConfigAccount = await connection.getAccountInfo(protocolConfigPda);
+    const faucetAccount = await connection.getAccountInfo(faucetPda);
+    const credLedgerAccount = await connection.getAccountInfo(credLedgerPda);
 
     // Initialize InsightPool
     if (!insightPoolAccount) {
@@ -113,6 +134,71 @@ async function main() {
         console.log('✓ AgentExecutor already initialized');
     }
 
+    // Initialize ProtocolConfig
+    if (!protocolConfigAccount) {
+        console.log('⏳ Initializing ProtocolConfig...');
+        try {
+            const tx = await program.methods
+                .initializeProtocolConfig()
+                .accounts({
+                    protocolConfig: protocolConfigPda,
+                    authority: keypair.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([keypair])
+                .rpc();
+            console.log('✅ ProtocolConfig initialized. Tx:', tx);
+        } catch (err: any) {
+            console.error('❌ Failed to initialize ProtocolConfig:', err.message);
+        }
+    } else {
+        console.log('✓ ProtocolConfig already initialized');
+    }
+
+    // Initialize the Cred faucet
+    if (!faucetAccount) {
+        console.log('⏳ Initializing Faucet...');
+        try {
+            const tx = await program.methods
+                .initializeFaucet()
+                .accounts({
+                    faucet: faucetPda,
+                    protocolConfig: protocolConfigPda,
+                    authority: keypair.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([keypair])
+                .rpc();
+            console.log('✅ Faucet initialized. Tx:', tx);
+        } catch (err: any) {
+            console.error('❌ Failed to initialize Faucet:', err.message);
+        }
+    } else {
+        console.log('✓ Faucet already initialized');
+    }
+
+    // Initialize the Cred ledger
+    if (!credLedgerAccount) {
+        console.log('⏳ Initializing CredLedger...');
+        try {
+            const tx = await program.methods
+                .initializeCredLedger()
+                .accounts({
+                    credLedger: credLedgerPda,
+                    protocolConfig: protocolConfigPda,
+                    authority: keypair.publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .signers([keypair])
+                .rpc();
+            console.log('✅ CredLedger initialized. Tx:', tx);
+        } catch (err: any) {
+            console.error('❌ Failed to initialize CredLedger:', err.message);
+        }
+    } else {
+        console.log('✓ CredLedger already initialized');
+    }
+
     console.log('\n🎉 Initialization complete!');
     console.log('\nNext steps:');
     console.log('1. Start the agent:    cd agent && npm run dev');
@@ -2,6 +2,15 @@ const anchor = require("@coral-xyz/anchor");
 const { Program } = require("@coral-xyz/anchor");
 const { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } = require("@solana/web3.js");
 const { expect } = require("chai");
+const {
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
+  createAssociatedTokenAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getExtensionTypes,
+  getMint,
+} = require("@solana/spl-token");
 
 describe("prophecy", () => {
   // Configure the client to use the local cluster
@@ -22,6 +31,8 @@ describe("prophecy", () => {
   let insightPoolBump;
   let agentExecutorPda;
   let agentExecutorBump;
+  let protocolConfigPda;
+  let credLedgerPda;
 
   // Use short market IDs to avoid PDA length issues
   const marketId = "mkt001";
@@ -57,6 +68,16 @@ describe("prophecy", () => {
       [Buffer.from("agent_executor")],
       program.programId
     );
+
+    [protocolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("protocol_config")],
+      program.programId
+    );
+
+    [credLedgerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cred_ledger")],
+      program.programId
+    );
   });
 
   describe("Initialization", () => {
@@ -91,6 +112,128 @@ describe("prophecy", () => {
       expect(agentExecutor.authority.toBase58()).to.equal(agentExecutorAuthority.publicKey.toBase58());
       expect(agentExecutor.marketsResolved.toNumber()).to.equal(0);
     });
+
+    it("Initializes the Protocol Config", async () => {
+      await program.methods
+        .initializeProtocolConfig()
+        .accounts({
+          protocolConfig: protocolConfigPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+      expect(config.authority.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+      expect(config.invalidOraclePolicy).to.deep.equal({ stakersLose: {} });
+      expect(config.credMint).to.equal(null);
+    });
+
+    it("Initializes the Cred ledger", async () => {
+      await program.methods
+        .initializeCredLedger()
+        .accounts({
+          credLedger: credLedgerPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const ledger = await program.account.credLedger.fetch(credLedgerPda);
+      expect(ledger.totalMinted.toNumber()).to.equal(0);
+      expect(ledger.totalStaked.toNumber()).to.equal(0);
+    });
+
+    it("Opens the first InsightPool emission epoch", async () => {
+      await program.methods
+        .crankEmission()
+        .accounts({ insightPool: insightPoolPda })
+        .rpc();
+
+      // Default schedule emits 5,000 Cred in the first epoch
+      const insightPool = await program.account.insightPool.fetch(insightPoolPda);
+      expect(insightPool.epoch.toNumber()).to.equal(1);
+      expect(insightPool.epochBudget.toNumber()).to.equal(5_000_000_000);
+      expect(insightPool.remainingBudget.toNumber()).to.equal(5_000_000_000);
+
+      try {
+        await program.methods
+          .crankEmission()
+          .accounts({ insightPool: insightPoolPda })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("EmissionNotDue");
+      }
+    });
+
+    it("Rejects Cred mint initialization from a non-authority", async () => {
+      const [credMintPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_mint")],
+        program.programId
+      );
+      const token2022ProgramId = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+      try {
+        await program.methods
+          .initializeCredMint(true)
+          .accounts({
+            credMint: credMintPda,
+            protocolConfig: protocolConfigPda,
+            authority: user1.publicKey,
+            tokenProgram: token2022ProgramId,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedConfigAuthority");
+      }
+
+      // Cred stays in vault-balance mode for the rest of the suite
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+      expect(config.credMint).to.equal(null);
+    });
+
+    it("Issues identity attestations for test users", async () => {
+      // Register the provider wallet as an approved attester
+      const [attesterPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attester"), provider.wallet.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .addAttester(provider.wallet.publicKey)
+        .accounts({
+          attesterAccount: attesterPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      for (const user of [user1, user2]) {
+        const [attestationPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("attestation"), provider.wallet.publicKey.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        );
+        const handleHash = Array.from(Buffer.alloc(32, user === user1 ? 1 : 2));
+        await program.methods
+          .issueAttestation({ twitterHandle: {} }, handleHash, new anchor.BN(0))
+          .accounts({
+            attestation: attestationPda,
+            subject: user.publicKey,
+            attesterAccount: attesterPda,
+            attester: provider.wallet.publicKey,
+          })
+          .rpc();
+
+        const attestation = await program.account.attestation.fetch(attestationPda);
+        expect(attestation.subject.toBase58()).to.equal(user.publicKey.toBase58());
+        expect(attestation.attestationType).to.deep.equal({ twitterHandle: {} });
+        expect(attestation.rewarded).to.equal(false);
+      }
+
+      const attester = await program.account.attester.fetch(attesterPda);
+      expect(attester.attestationsIssued.toNumber()).to.equal(2);
+    });
   });
 
   describe("Reputation Vault", () => {
@@ -104,21 +247,29 @@ describe("prophecy", () => {
     });
 
     it("Initializes a user's Reputation Vault with initial Cred", async () => {
-      // Note: owner and payer are separate - payer pays for account creation
+      // Note: owner and payer are separate - payer pays for account creation.
+      // The attested owner co-signs to receive the full grant.
+      const [attestationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), provider.wallet.publicKey.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
       await program.methods
         .initializeReputationVault()
         .accounts({
           reputationVault: user1VaultPda,
           owner: user1.publicKey,
           payer: provider.wallet.publicKey, // Provider wallet pays
+          attestation: attestationPda,
           systemProgram: SystemProgram.programId,
         })
-        .rpc(); // No extra signers needed - provider wallet signs
+        .signers([user1])
+        .rpc();
 
       const vault = await program.account.reputationVault.fetch(user1VaultPda);
       expect(vault.owner.toBase58()).to.equal(user1.publicKey.toBase58());
       expect(vault.credBalance.toNumber()).to.equal(100_000_000); // 100 Cred (6 decimals)
       expect(vault.totalEarned.toNumber()).to.equal(100_000_000);
+      expect(vault.lockedGrant.toNumber()).to.equal(0);
       expect(vault.participationCount.toNumber()).to.equal(0);
     });
 
@@ -252,14 +403,20 @@ describe("prophecy", () => {
       );
 
       // Initialize user2 vault first (with provider as payer)
+      const [attestationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), provider.wallet.publicKey.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
       await program.methods
         .initializeReputationVault()
         .accounts({
           reputationVault: user2VaultPda,
           owner: user2.publicKey,
           payer: provider.wallet.publicKey,
+          attestation: attestationPda,
           systemProgram: SystemProgram.programId,
         })
+        .signers([user2])
         .rpc();
 
       const [user2StakePda] = PublicKey.findProgramAddressSync(
@@ -300,11 +457,16 @@ describe("prophecy", () => {
 
     it("Submits evidence with IPFS CID", async () => {
       const ipfsCid = "bafkreiexamplecid123456789abc";
+      const [evidencePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("evidence"), marketPda.toBuffer(), Buffer.from([1])],
+        program.programId
+      );
 
       await program.methods
         .submitEvidence(ipfsCid)
         .accounts({
           market: marketPda,
+          evidence: evidencePda,
           user: user1.publicKey,
         })
         .signers([user1])
@@ -312,6 +474,11 @@ describe("prophecy", () => {
 
       const market = await program.account.market.fetch(marketPda);
       expect(market.evidenceCount).to.equal(1);
+
+      const evidence = await program.account.evidence.fetch(evidencePda);
+      expect(evidence.submitter.toBase58()).to.equal(user1.publicKey.toBase58());
+      expect(evidence.index).to.equal(1);
+      expect(evidence.ipfsCid).to.equal(ipfsCid);
     });
   });
 
@@ -330,7 +497,7 @@ describe("prophecy", () => {
 
       try {
         await program.methods
-          .resolveMarket(1, ipfsHash) // Try to resolve as YES
+          .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // Try to resolve as YES
           .accounts({
             market: marketPda,
             agentExecutor: agentExecutorPda,
@@ -348,7 +515,7 @@ describe("prophecy", () => {
       const ipfsHash = new Array(32).fill(42); // Mock hash
 
       await program.methods
-        .resolveMarket(1, ipfsHash) // Resolve as YES
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // Resolve as YES
         .accounts({
           market: marketPda,
           agentExecutor: agentExecutorPda,
@@ -360,9 +527,13 @@ describe("prophecy", () => {
       const market = await program.account.market.fetch(marketPda);
       expect(market.status).to.deep.equal({ resolved: {} });
       expect(market.outcome).to.equal(1); // YES
+      expect(market.confidenceBps).to.equal(9000);
+      expect(market.modelId).to.equal("test-model");
+      expect(market.judgeHash).to.deep.equal(ipfsHash);
 
       const agentExecutor = await program.account.agentExecutor.fetch(agentExecutorPda);
       expect(agentExecutor.marketsResolved.toNumber()).to.equal(1);
+      expect(agentExecutor.resolutionsUpheld.toNumber()).to.equal(1);
     });
 
     it("Cannot resolve already resolved market", async () => {
@@ -370,7 +541,7 @@ describe("prophecy", () => {
 
       try {
         await program.methods
-          .resolveMarket(0, ipfsHash)
+          .resolveMarket(0, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
           .accounts({
             market: marketPda,
             agentExecutor: agentExecutorPda,
@@ -383,259 +554,718 @@ describe("prophecy", () => {
         expect(err.message).to.include("MarketNotOpen");
       }
     });
-  });
-
-  describe("Sponsor Escrow (Mock)", () => {
-    const sponsoredMarketId = "spmkt01";
-    let sponsoredMarketPda;
-    let escrowPda;
 
-    before(async () => {
-      [sponsoredMarketPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("market"), Buffer.from(sponsoredMarketId)],
+    it("Holds Cred claims until the resolution is final", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
         program.programId
       );
-      [escrowPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("sponsor_escrow"), sponsoredMarketPda.toBuffer()],
+      const [credStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
         program.programId
       );
 
-      // Create sponsored market (note param order: tweet_url, market_id, sponsor)
+      // Still inside the dispute window, where a dispute could re-settle the market
+      try {
+        await program.methods
+          .claimCredStake()
+          .accounts({
+            market: marketPda,
+            credStake: credStakePda,
+            reputationVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotFinal");
+      }
+
+      const stake = await program.account.credStake.fetch(credStakePda);
+      expect(stake.claimed).to.equal(false);
+
+      // Let resolutions become final at once for the settlement tests that follow
       await program.methods
-        .initializeMarket(tweetUrl, sponsoredMarketId, sponsor.publicKey)
-        .accounts({
-          market: sponsoredMarketPda,
-          agentExecutor: agentExecutorPda,
-          creator: marketCreator.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([marketCreator])
+        .updateProtocolConfig({ disputeWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
         .rpc();
     });
 
-    it("Initializes sponsor escrow", async () => {
-      const depositAmount = new anchor.BN(1 * LAMPORTS_PER_SOL);
+    it("Claims the pari-mutuel payout for a winning stake", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [credStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
 
       await program.methods
-        .initializeSponsorEscrow(depositAmount)
+        .claimCredStake()
         .accounts({
-          escrow: escrowPda,
-          market: sponsoredMarketPda,
-          sponsor: sponsor.publicKey,
-          systemProgram: SystemProgram.programId,
+          market: marketPda,
+          credStake: credStakePda,
+          reputationVault: user1VaultPda,
         })
-        .signers([sponsor])
         .rpc();
 
-      const escrow = await program.account.sponsorEscrow.fetch(escrowPda);
-      expect(escrow.sponsor.toBase58()).to.equal(sponsor.publicKey.toBase58());
-      expect(escrow.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
-      expect(escrow.isReleased).to.equal(false);
-    });
-  });
+      // Only YES stake in the pool, so the winner takes back the whole 50 Cred pool
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(vaultBefore.credBalance.toNumber() + 50_000_000);
+      expect(vaultAfter.wins.toNumber()).to.equal(vaultBefore.wins.toNumber() + 1);
+      expect(vaultAfter.settledCount.toNumber()).to.equal(vaultBefore.settledCount.toNumber() + 1);
+      expect(vaultAfter.brierScoreTotal.toNumber()).to.equal(vaultBefore.brierScoreTotal.toNumber()); // Perfect forecast
 
-  describe("Cred Earning", () => {
-    let user1VaultPda;
+      const stake = await program.account.credStake.fetch(credStakePda);
+      expect(stake.claimed).to.equal(true);
 
-    before(async () => {
-      [user1VaultPda] = PublicKey.findProgramAddressSync(
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.totalPaidOut.toNumber()).to.equal(50_000_000);
+      expect(market.settledStake.toNumber()).to.equal(50_000_000);
+    });
+
+    it("Distributes InsightPool rewards out of the current epoch budget", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
         program.programId
       );
-    });
-
-    it("Earns Cred via agent executor", async () => {
-      const earnAmount = new anchor.BN(25_000_000); // 25 Cred
+      const [credStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const distribute = (amount, authority = agentExecutorAuthority) =>
+        program.methods
+          .distributeInsightRewards(new anchor.BN(amount))
+          .accounts({
+            market: marketPda,
+            insightPool: insightPoolPda,
+            credStake: credStakePda,
+            recipientVault: user1VaultPda,
+            agentExecutor: agentExecutorPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
 
       const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
-      const balanceBefore = vaultBefore.credBalance.toNumber();
+      const poolBefore = await program.account.insightPool.fetch(insightPoolPda);
 
-      await program.methods
-        .earnCred(earnAmount, { correctPrediction: {} }) // EarnMethod enum
-        .accounts({
-          reputationVault: user1VaultPda,
-          agentExecutor: agentExecutorPda,
-          authority: agentExecutorAuthority.publicKey,
-        })
-        .signers([agentExecutorAuthority])
-        .rpc();
+      // Nothing beyond what is left of the epoch budget
+      try {
+        await distribute(poolBefore.remainingBudget.toNumber() + 1);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("EmissionBudgetExhausted");
+      }
+
+      // Only the executor authority can distribute
+      try {
+        await distribute(10_000_000, user2);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedMinter");
+      }
 
+      await distribute(10_000_000);
+
+      // 90% confidence is above the full-reward threshold, so the whole 10 Cred is paid
       const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
-      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore + 25_000_000);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(vaultBefore.credBalance.toNumber() + 10_000_000);
+      const poolAfter = await program.account.insightPool.fetch(insightPoolPda);
+      expect(poolAfter.remainingBudget.toNumber()).to.equal(poolBefore.remainingBudget.toNumber() - 10_000_000);
+      expect(poolAfter.epochBudget.toNumber()).to.equal(poolBefore.epochBudget.toNumber());
+      expect(poolAfter.totalCredits.toNumber()).to.equal(poolBefore.totalCredits.toNumber() + 10_000_000);
+      expect(poolAfter.distributionsCount.toNumber()).to.equal(poolBefore.distributionsCount.toNumber() + 1);
+      const market = await program.account.market.fetch(marketPda);
+      expect(market.insightPoolAmount.toNumber()).to.equal(10_000_000);
+      const stake = await program.account.credStake.fetch(credStakePda);
+      expect(stake.insightDistributed).to.equal(true);
+
+      // The same stake is not rewarded twice
+      try {
+        await distribute(10_000_000);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InsightAlreadyDistributed");
+      }
     });
-  });
 
-  describe("Market Dispute", () => {
-    const disputeMarketId = "dmkt01";
-    let disputeMarketPda;
+    it("Opens later emission epochs with a decayed budget", async () => {
+      await program.methods
+        .updateProtocolConfig({ emissionEpochDuration: new anchor.BN(1) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 2000));
 
-    before(async () => {
-      [disputeMarketPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("market"), Buffer.from(disputeMarketId)],
+      const poolBefore = await program.account.insightPool.fetch(insightPoolPda);
+      await program.methods
+        .crankEmission()
+        .accounts({ insightPool: insightPoolPda })
+        .rpc();
+      const poolAfter = await program.account.insightPool.fetch(insightPoolPda);
+
+      // 5,000 Cred shrinking by 2% per elapsed epoch, mirroring decayed_emission
+      const scale = BigInt(1_000_000_000_000);
+      let factor = (BigInt(9_800) * scale) / BigInt(10_000);
+      let multiplier = scale;
+      for (let remaining = poolAfter.epoch.toNumber() - 1; remaining > 0; remaining >>= 1) {
+        if (remaining & 1) multiplier = (multiplier * factor) / scale;
+        factor = (factor * factor) / scale;
+      }
+      const expected = Number((BigInt(5_000_000_000) * multiplier) / scale);
+
+      expect(poolAfter.epoch.toNumber()).to.be.greaterThan(poolBefore.epoch.toNumber());
+      expect(poolAfter.epochBudget.toNumber()).to.equal(expected);
+      expect(poolAfter.epochBudget.toNumber()).to.be.lessThan(5_000_000_000);
+      // Unspent budget does not carry over
+      expect(poolAfter.remainingBudget.toNumber()).to.equal(expected);
+      expect(poolAfter.totalEmitted.toNumber()).to.equal(poolBefore.totalEmitted.toNumber() + expected);
+
+      await program.methods
+        .updateProtocolConfig({ emissionEpochDuration: new anchor.BN(7 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    it("Returns stakes at par when no one staked on the winning side", async () => {
+      const oneSidedMarketId = "mkt1side";
+      const [oneSidedMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(oneSidedMarketId)],
+        program.programId
+      );
+      const [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const [credStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), oneSidedMarketPda.toBuffer(), user2.publicKey.toBuffer()],
         program.programId
       );
 
-      // Create and resolve a market to dispute
       await program.methods
-        .initializeMarket(tweetUrl, disputeMarketId, null)
+        .initializeMarket(tweetUrl, oneSidedMarketId, null)
         .accounts({
-          market: disputeMarketPda,
+          market: oneSidedMarketPda,
           agentExecutor: agentExecutorPda,
           creator: marketCreator.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([marketCreator])
         .rpc();
+      await program.methods
+        .stakeCred(false, new anchor.BN(3_000_000))
+        .accounts({
+          market: oneSidedMarketPda,
+          reputationVault: user2VaultPda,
+          credStake: credStakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
 
-      const ipfsHash = new Array(32).fill(1);
+      const ipfsHash = new Array(32).fill(5);
       await program.methods
-        .resolveMarket(1, ipfsHash)
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES, with only NO stakes
         .accounts({
-          market: disputeMarketPda,
+          market: oneSidedMarketPda,
           agentExecutor: agentExecutorPda,
           authority: agentExecutorAuthority.publicKey,
         })
         .signers([agentExecutorAuthority])
         .rpc();
-    });
 
-    it("Disputes a resolved market", async () => {
+      const vaultBefore = await program.account.reputationVault.fetch(user2VaultPda);
       await program.methods
-        .disputeMarket()
+        .claimCredStake()
         .accounts({
-          market: disputeMarketPda,
-          disputer: user1.publicKey,
+          market: oneSidedMarketPda,
+          credStake: credStakePda,
+          reputationVault: user2VaultPda,
         })
-        .signers([user1])
         .rpc();
+      const vaultAfter = await program.account.reputationVault.fetch(user2VaultPda);
 
-      const market = await program.account.market.fetch(disputeMarketPda);
-      expect(market.status).to.deep.equal({ disputed: {} });
-    });
-  });
-
-  describe("Oracle Stakes", () => {
-    const oracleMarketId = "omkt01";
-    let oracleMarketPda;
-    let user1OracleStakePda;
+      // The losing stake comes back at par, net of any grant tranche the settlement unlocked
+      const unlocked = vaultBefore.lockedGrant.toNumber() - vaultAfter.lockedGrant.toNumber();
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(3_000_000 + unlocked);
 
-    before(async () => {
-      [oracleMarketPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("market"), Buffer.from(oracleMarketId)],
-        program.programId
-      );
-      [user1OracleStakePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("oracle_stake"), oracleMarketPda.toBuffer(), user1.publicKey.toBuffer()],
-        program.programId
-      );
+      const market = await program.account.market.fetch(oneSidedMarketPda);
+      expect(market.totalPaidOut.toNumber()).to.equal(3_000_000);
+      await program.methods.auditMarket().accounts({ market: oneSidedMarketPda }).rpc();
+    });
 
-      // Need user1 vault (already exists from previous test)
-      const [user1VaultPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+    it("Scores each side of a partial-truth settlement", async () => {
+      const partialMarketId = "mktpart";
+      const [partialMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(partialMarketId)],
         program.programId
       );
+      const stakers = [
+        { user: user1, direction: true },
+        { user: user2, direction: false },
+      ].map(({ user, direction }) => ({
+        user,
+        direction,
+        vault: PublicKey.findProgramAddressSync(
+          [Buffer.from("reputation_vault"), user.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        stake: PublicKey.findProgramAddressSync(
+          [Buffer.from("cred_stake"), partialMarketPda.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0],
+      }));
 
-      // Create a new market for oracle stake testing
       await program.methods
-        .initializeMarket(tweetUrl, oracleMarketId, null)
+        .initializeMarket(tweetUrl, partialMarketId, null)
         .accounts({
-          market: oracleMarketPda,
+          market: partialMarketPda,
           agentExecutor: agentExecutorPda,
           creator: marketCreator.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([marketCreator])
         .rpc();
-    });
+      for (const { user, direction, vault, stake } of stakers) {
+        await program.methods
+          .stakeCred(direction, new anchor.BN(2_000_000))
+          .accounts({
+            market: partialMarketPda,
+            reputationVault: vault,
+            credStake: stake,
+            user: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+      }
 
-    it("Stakes on oracle for a market", async () => {
-      const [user1VaultPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
-        program.programId
+      const ipfsHash = new Array(32).fill(6);
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, 7000) // 70% true
+        .accounts({
+          market: partialMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      // YES forecast (100%) is 30 points off: a win with Brier 900 and 70% of the 4 Cred pool.
+      // NO forecast (0%) is 70 points off: a loss with Brier 4900 and the other 30%.
+      const expected = [
+        { wins: 1, losses: 0, brier: 900, payout: 2_800_000 },
+        { wins: 0, losses: 1, brier: 4_900, payout: 1_200_000 },
+      ];
+      for (const [i, { vault, stake }] of stakers.entries()) {
+        const before = await program.account.reputationVault.fetch(vault);
+        await program.methods
+          .claimCredStake()
+          .accounts({ market: partialMarketPda, credStake: stake, reputationVault: vault })
+          .rpc();
+        const after = await program.account.reputationVault.fetch(vault);
+
+        const unlocked = before.lockedGrant.toNumber() - after.lockedGrant.toNumber();
+        expect(after.credBalance.toNumber() - before.credBalance.toNumber()).to.equal(expected[i].payout + unlocked);
+        expect(after.wins.toNumber() - before.wins.toNumber()).to.equal(expected[i].wins);
+        expect(after.losses.toNumber() - before.losses.toNumber()).to.equal(expected[i].losses);
+        expect(after.settledCount.toNumber()).to.equal(before.settledCount.toNumber() + 1);
+        expect(after.brierScoreTotal.toNumber() - before.brierScoreTotal.toNumber()).to.equal(expected[i].brier);
+      }
+
+      const market = await program.account.market.fetch(partialMarketPda);
+      expect(market.settlementBps).to.equal(7000);
+      expect(market.totalPaidOut.toNumber()).to.equal(4_000_000);
+      expect(market.settledStake.toNumber()).to.equal(4_000_000);
+    });
+
+    it("Audits the settled market's Cred flows", async () => {
+      await program.methods.auditMarket().accounts({ market: marketPda }).rpc();
+
+      const ledger = await program.account.credLedger.fetch(credLedgerPda);
+      expect(ledger.totalPaidOut.toNumber()).to.be.at.least(50_000_000);
+      expect(ledger.totalStaked.toNumber()).to.be.at.least(ledger.totalPaidOut.toNumber());
+    });
+  });
+
+  describe("Sponsor Escrow", () => {
+    const sponsoredMarketId = "spmkt01";
+    const winnerlessMarketId = "spmkt02";
+    let sponsoredMarketPda;
+    let escrowPda;
+    let winnerlessMarketPda;
+    let winnerlessEscrowPda;
+    let user1VaultPda;
+    let user2VaultPda;
+    let user1StakePda;
+    let user2StakePda;
+
+    const ipfsHash = new Array(32).fill(9);
+
+    before(async () => {
+      [sponsoredMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(sponsoredMarketId)],
+        program.programId
+      );
+      [escrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("sponsor_escrow"), sponsoredMarketPda.toBuffer()],
+        program.programId
+      );
+      [winnerlessMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(winnerlessMarketId)],
+        program.programId
+      );
+      [winnerlessEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("sponsor_escrow"), winnerlessMarketPda.toBuffer()],
+        program.programId
+      );
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      [user1StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), sponsoredMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), sponsoredMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
       );
 
-      const stakeAmount = new anchor.BN(10_000_000); // 10 Cred
+      // Create sponsored market (note param order: tweet_url, market_id, sponsor)
+      await program.methods
+        .initializeMarket(tweetUrl, sponsoredMarketId, sponsor.publicKey)
+        .accounts({
+          market: sponsoredMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
 
-      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
-      const balanceBefore = vaultBefore.credBalance.toNumber();
+      await program.methods
+        .initializeMarket(tweetUrl, winnerlessMarketId, null)
+        .accounts({
+          market: winnerlessMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+    });
+
+    it("Initializes sponsor escrow", async () => {
+      const depositAmount = new anchor.BN(1 * LAMPORTS_PER_SOL);
+
+      // The market reserves its escrow for the named sponsor
+      try {
+        await program.methods
+          .initializeSponsorEscrow(depositAmount, "Impostor", "")
+          .accounts({
+            sponsorEscrow: escrowPda,
+            market: sponsoredMarketPda,
+            sponsor: user1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedSponsor");
+      }
 
       await program.methods
-        .stakeOnOracle(stakeAmount)
+        .initializeSponsorEscrow(depositAmount, "Acme", "https://acme.example/sponsor.json")
         .accounts({
-          market: oracleMarketPda,
+          sponsorEscrow: escrowPda,
+          market: sponsoredMarketPda,
+          sponsor: sponsor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sponsor])
+        .rpc();
+
+      const escrow = await program.account.sponsorEscrow.fetch(escrowPda);
+      expect(escrow.sponsor.toBase58()).to.equal(sponsor.publicKey.toBase58());
+      expect(escrow.amount.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(escrow.mint).to.be.null;
+      expect(escrow.sponsorName).to.equal("Acme");
+      expect(escrow.isReleased).to.equal(false);
+    });
+
+    it("Distributes the escrow to winning stakers once the market is final", async () => {
+      await program.methods
+        .stakeCred(true, new anchor.BN(3_000_000))
+        .accounts({
+          market: sponsoredMarketPda,
           reputationVault: user1VaultPda,
-          oracleStake: user1OracleStakePda,
+          credStake: user1StakePda,
           user: user1.publicKey,
           systemProgram: SystemProgram.programId,
         })
         .signers([user1])
         .rpc();
+      await program.methods
+        .stakeCred(false, new anchor.BN(1_000_000))
+        .accounts({
+          market: sponsoredMarketPda,
+          reputationVault: user2VaultPda,
+          credStake: user2StakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
 
-      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
-      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore - 10_000_000);
-
-      const oracleStake = await program.account.oracleStake.fetch(user1OracleStakePda);
-      expect(oracleStake.amount.toNumber()).to.equal(10_000_000);
-      expect(oracleStake.claimed).to.equal(false);
-      expect(oracleStake.user.toBase58()).to.equal(user1.publicKey.toBase58());
-    });
-
-    it("Rejects oracle stake on resolved market", async () => {
-      // Resolve the market first
-      const ipfsHash = new Array(32).fill(99);
       await program.methods
-        .resolveMarket(1, ipfsHash)
+        .updateProtocolConfig({ disputeWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES
         .accounts({
-          market: oracleMarketPda,
+          market: sponsoredMarketPda,
           agentExecutor: agentExecutorPda,
           authority: agentExecutorAuthority.publicKey,
         })
         .signers([agentExecutorAuthority])
         .rpc();
 
-      // Try to stake on resolved market with user2
-      const [user2VaultPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+      const claim = (stakePda, staker: PublicKey) =>
+        program.methods
+          .claimSponsorReward()
+          .accounts({
+            market: sponsoredMarketPda,
+            credStake: stakePda,
+            staker,
+          })
+          .rpc();
+
+      try {
+        await claim(user2StakePda, user2.publicKey);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("NotAWinningStake");
+      }
+
+      const lamportsBefore = await provider.connection.getBalance(user1.publicKey);
+      await claim(user1StakePda, user1.publicKey);
+      const lamportsAfter = await provider.connection.getBalance(user1.publicKey);
+      expect(lamportsAfter - lamportsBefore).to.equal(LAMPORTS_PER_SOL);
+
+      const escrow = await program.account.sponsorEscrow.fetch(escrowPda);
+      expect(escrow.distributed.toNumber()).to.equal(LAMPORTS_PER_SOL);
+
+      try {
+        await claim(user1StakePda, user1.publicKey);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("SponsorRewardAlreadyClaimed");
+      }
+
+      // A market with winners is not refundable to the sponsor
+      try {
+        await program.methods
+          .refundSponsorEscrow()
+          .accounts({ market: sponsoredMarketPda, sponsor: sponsor.publicKey })
+          .signers([sponsor])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("SponsorEscrowNotRefundable");
+      }
+    });
+
+    it("Returns the unclaimed remainder to the sponsor once the claim window closes", async () => {
+      const [marketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("spmkt03")],
         program.programId
       );
-      const [user2OracleStakePda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("oracle_stake"), oracleMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+      const [remainderEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("sponsor_escrow"), marketPda.toBuffer()],
         program.programId
       );
+      const stakeOf = (user: Keypair) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("cred_stake"), marketPda.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0];
 
-      try {
+      await program.methods
+        .initializeMarket(tweetUrl, "spmkt03", sponsor.publicKey)
+        .accounts({
+          market: marketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      await program.methods
+        .initializeSponsorEscrow(new anchor.BN(LAMPORTS_PER_SOL), "Acme", "")
+        .accounts({
+          sponsorEscrow: remainderEscrowPda,
+          market: marketPda,
+          sponsor: sponsor.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sponsor])
+        .rpc();
+      for (const [user, vault, amount] of [
+        [user1, user1VaultPda, 3_000_000],
+        [user2, user2VaultPda, 1_000_000],
+      ] as [Keypair, PublicKey, number][]) {
         await program.methods
-          .stakeOnOracle(new anchor.BN(10_000_000))
+          .stakeCred(true, new anchor.BN(amount))
           .accounts({
-            market: oracleMarketPda,
-            reputationVault: user2VaultPda,
-            oracleStake: user2OracleStakePda,
-            user: user2.publicKey,
+            market: marketPda,
+            reputationVault: vault,
+            credStake: stakeOf(user),
+            user: user.publicKey,
             systemProgram: SystemProgram.programId,
           })
-          .signers([user2])
+          .signers([user])
           .rpc();
-        expect.fail("Should have thrown an error");
-      } catch (err) {
-        expect(err.message).to.include("MarketNotOpen");
+      }
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES
+        .accounts({
+          market: marketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      const claim = (user: Keypair) =>
+        program.methods
+          .claimSponsorReward()
+          .accounts({ market: marketPda, credStake: stakeOf(user), staker: user.publicKey })
+          .rpc();
+      const refund = () =>
+        program.methods
+          .refundSponsorEscrow()
+          .accounts({ market: marketPda, sponsor: sponsor.publicKey })
+          .signers([sponsor])
+          .rpc();
+
+      await claim(user1);
+
+      // user2's share stays claimable while the window is open
+      try {
+        await refund();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("SponsorEscrowNotRefundable");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      // The unclaimed quarter comes back with the closed escrow's rent
+      const escrowLamports = await provider.connection.getBalance(remainderEscrowPda);
+      const lamportsBefore = await provider.connection.getBalance(sponsor.publicKey);
+      await refund();
+      const lamportsAfter = await provider.connection.getBalance(sponsor.publicKey);
+      expect(escrowLamports).to.be.greaterThan(LAMPORTS_PER_SOL / 4);
+      expect(lamportsAfter - lamportsBefore).to.be.closeTo(escrowLamports, 10_000);
+      expect(await program.account.sponsorEscrow.fetchNullable(remainderEscrowPda)).to.equal(null);
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      try {
+        await claim(user2);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("AccountNotInitialized");
       }
     });
 
-    it("Resolves oracle stakes after market resolution", async () => {
-      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+    it("Refunds the sponsor when a final market has no winning stake", async () => {
+      const openEscrow = (creator?: Keypair) =>
+        program.methods
+          .initializeSponsorEscrow(new anchor.BN(LAMPORTS_PER_SOL / 2), "Acme", "")
+          .accounts({
+            sponsorEscrow: winnerlessEscrowPda,
+            market: winnerlessMarketPda,
+            sponsor: sponsor.publicKey,
+            creator: creator ? creator.publicKey : null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers(creator ? [sponsor, creator] : [sponsor])
+          .rpc();
+
+      // With no sponsor reserved, the creator must approve whoever opens the escrow
+      try {
+        await openEscrow();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("UnauthorizedSponsor");
+      }
+      await openEscrow(marketCreator);
+      await program.methods
+        .resolveMarket(0, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // NO
+        .accounts({
+          market: winnerlessMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      const escrowLamports = await provider.connection.getBalance(winnerlessEscrowPda);
+      const lamportsBefore = await provider.connection.getBalance(sponsor.publicKey);
+      await program.methods
+        .refundSponsorEscrow()
+        .accounts({ market: winnerlessMarketPda, sponsor: sponsor.publicKey })
+        .signers([sponsor])
+        .rpc();
+      const lamportsAfter = await provider.connection.getBalance(sponsor.publicKey);
+      // The whole deposit and the escrow's rent come back; the sponsor also pays the transaction fee
+      expect(escrowLamports).to.be.greaterThan(LAMPORTS_PER_SOL / 2);
+      expect(lamportsAfter - lamportsBefore).to.be.closeTo(escrowLamports, 10_000);
+
+      // The escrow is closed
+      expect(await program.account.sponsorEscrow.fetchNullable(winnerlessEscrowPda)).to.equal(null);
+
+      await program.methods
+        .updateProtocolConfig({ disputeWindow: new anchor.BN(2 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+    });
+  });
+
+  describe("Cred Earning", () => {
+    let user1VaultPda;
+
+    before(async () => {
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
         [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
         program.programId
       );
+    });
+
+    it("Earns Cred via agent executor", async () => {
+      const earnAmount = new anchor.BN(25_000_000); // 25 Cred
 
       const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
       const balanceBefore = vaultBefore.credBalance.toNumber();
 
-      // Market was resolved without dispute, so oracle staker wins 2x
       await program.methods
-        .resolveOracleStake(false) // market was NOT disputed
+        .earnCred(earnAmount, { correctPrediction: {} }) // EarnMethod enum
         .accounts({
-          market: oracleMarketPda,
-          oracleStake: user1OracleStakePda,
           reputationVault: user1VaultPda,
           agentExecutor: agentExecutorPda,
           authority: agentExecutorAuthority.publicKey,
@@ -644,11 +1274,2862 @@ describe("prophecy", () => {
         .rpc();
 
       const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
-      // Should have received 2x stake (20 Cred)
-      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore + 20_000_000);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore + 25_000_000);
+      // EarnMethod::CorrectPrediction
+      expect(vaultAfter.earnedTodayByMethod[2].toNumber()).to.equal(25_000_000);
+      expect(vaultAfter.earnedByMethod[2].toNumber()).to.equal(
+        vaultBefore.earnedByMethod[2].toNumber() + 25_000_000
+      );
+    });
 
-      const oracleStake = await program.account.oracleStake.fetch(user1OracleStakePda);
-      expect(oracleStake.claimed).to.equal(true);
+    it("Rejects earn_cred beyond the method's daily cap", async () => {
+      // Default CorrectPrediction cap is 200 Cred per vault per day
+      try {
+        await program.methods
+          .earnCred(new anchor.BN(180_000_000), { correctPrediction: {} })
+          .accounts({
+            reputationVault: user1VaultPda,
+            agentExecutor: agentExecutorPda,
+            authority: agentExecutorAuthority.publicKey,
+          })
+          .signers([agentExecutorAuthority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("EarnMethodCapExceeded");
+      }
+    });
+
+    it("Rejects earn_cred for methods paid by their own instructions", async () => {
+      for (const method of [{ referral: {} }, { identityVerification: {} }]) {
+        try {
+          await program.methods
+            .earnCred(new anchor.BN(1_000_000), method)
+            .accounts({
+              reputationVault: user1VaultPda,
+              agentExecutor: agentExecutorPda,
+              authority: agentExecutorAuthority.publicKey,
+            })
+            .signers([agentExecutorAuthority])
+            .rpc();
+          expect.fail("Should have thrown");
+        } catch (error) {
+          expect(error.message).to.include("EarnMethodCapExceeded");
+        }
+      }
+    });
+  });
+
+  describe("Market Dispute", () => {
+    const disputeMarketId = "dmkt01";
+    let disputeMarketPda;
+    let user1VaultPda;
+    let user2VaultPda;
+    let user2StakePda;
+
+    before(async () => {
+      [disputeMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(disputeMarketId)],
+        program.programId
+      );
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), disputeMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Create and resolve a market to dispute
+      await program.methods
+        .initializeMarket(tweetUrl, disputeMarketId, null)
+        .accounts({
+          market: disputeMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+
+      // user2 backs NO, which the YES resolution below rules against
+      await program.methods
+        .stakeCred(false, new anchor.BN(5_000_000))
+        .accounts({
+          market: disputeMarketPda,
+          reputationVault: user2VaultPda,
+          credStake: user2StakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+    });
+
+    it("Posts a slashable executor bond", async () => {
+      await program.methods
+        .postExecutorBond(new anchor.BN(10_000_000))
+        .accounts({
+          agentExecutor: agentExecutorPda,
+          depositorVault: user1VaultPda,
+          depositor: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      const agentExecutor = await program.account.agentExecutor.fetch(agentExecutorPda);
+      expect(agentExecutor.bond.toNumber()).to.equal(10_000_000);
+    });
+
+    it("Disables resolution while the bond is below the minimum", async () => {
+      const ipfsHash = new Array(32).fill(1);
+      await program.methods
+        .updateProtocolConfig({ executorMinBond: new anchor.BN(50_000_000) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      try {
+        await program.methods
+          .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
+          .accounts({
+            market: disputeMarketPda,
+            agentExecutor: agentExecutorPda,
+            authority: agentExecutorAuthority.publicKey,
+          })
+          .signers([agentExecutorAuthority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ExecutorBondTooLow");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ executorMinBond: new anchor.BN(10_000_000) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
+        .accounts({
+          market: disputeMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+    });
+
+    it("Disputes a resolved market", async () => {
+      await program.methods
+        .disputeMarket()
+        .accounts({
+          market: disputeMarketPda,
+          disputer: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      const market = await program.account.market.fetch(disputeMarketPda);
+      expect(market.status).to.deep.equal({ disputed: {} });
+      expect(market.disputeStatus).to.deep.equal({ pending: {} });
+    });
+
+    it("Requires a corrected outcome and the disputer's vault to overturn", async () => {
+      try {
+        await program.methods
+          .settleDispute(false, 1) // Same as the disputed YES verdict
+          .accounts({
+            market: disputeMarketPda,
+            authority: provider.wallet.publicKey,
+            disputerVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidCorrectedOutcome");
+      }
+
+      try {
+        await program.methods
+          .settleDispute(false, 0)
+          .accounts({
+            market: disputeMarketPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("DisputerVaultRequired");
+      }
+
+      const market = await program.account.market.fetch(disputeMarketPda);
+      expect(market.disputeStatus).to.deep.equal({ pending: {} });
+    });
+
+    it("Overturns the resolution when settling the dispute", async () => {
+      const disputerBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      await program.methods
+        .settleDispute(false, 0) // Corrected to NO
+        .accounts({
+          market: disputeMarketPda,
+          authority: provider.wallet.publicKey,
+          disputerVault: user1VaultPda,
+        })
+        .rpc();
+
+      const market = await program.account.market.fetch(disputeMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.disputeStatus).to.deep.equal({ overturned: {} });
+      expect(market.outcome).to.equal(0);
+      expect(market.overturnedOutcome).to.equal(1);
+      expect(market.settlementBps).to.equal(0);
+
+      // 20% of the 10 Cred bond is slashed: a quarter to the disputer, the rest to stakers
+      const agentExecutor = await program.account.agentExecutor.fetch(agentExecutorPda);
+      expect(agentExecutor.resolutionsOverturned.toNumber()).to.equal(1);
+      expect(agentExecutor.bond.toNumber()).to.equal(8_000_000);
+      expect(agentExecutor.totalSlashed.toNumber()).to.equal(2_000_000);
+      expect(market.slashCompensation.toNumber()).to.equal(1_500_000);
+
+      const disputerAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(disputerAfter.credBalance.toNumber() - disputerBefore.credBalance.toNumber()).to.equal(500_000);
+
+      // The bond is now below the 10 Cred minimum; drop the requirement for later tests
+      await program.methods
+        .updateProtocolConfig({ executorMinBond: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      // A settled market cannot be disputed again
+      try {
+        await program.methods
+          .disputeMarket()
+          .accounts({
+            market: disputeMarketPda,
+            disputer: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("AlreadyDisputed");
+      }
+    });
+
+    it("Compensates stakers the overturned resolution ruled against", async () => {
+      const vaultBefore = await program.account.reputationVault.fetch(user2VaultPda);
+
+      await program.methods
+        .claimSlashCompensation()
+        .accounts({
+          market: disputeMarketPda,
+          credStake: user2StakePda,
+          reputationVault: user2VaultPda,
+        })
+        .rpc();
+
+      const vaultAfter = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(1_500_000);
+
+      try {
+        await program.methods
+          .claimSlashCompensation()
+          .accounts({
+            market: disputeMarketPda,
+            credStake: user2StakePda,
+            reputationVault: user2VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("CompensationAlreadyClaimed");
+      }
+    });
+
+    it("Settles Cred stakes on the corrected outcome", async () => {
+      const vaultBefore = await program.account.reputationVault.fetch(user2VaultPda);
+
+      await program.methods
+        .claimCredStake()
+        .accounts({
+          market: disputeMarketPda,
+          credStake: user2StakePda,
+          reputationVault: user2VaultPda,
+        })
+        .rpc();
+
+      // user2's NO stake now wins; as the only stake it comes back at par
+      const vaultAfter = await program.account.reputationVault.fetch(user2VaultPda);
+      const unlocked = vaultBefore.lockedGrant.toNumber() - vaultAfter.lockedGrant.toNumber();
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(5_000_000 + unlocked);
+      expect(vaultAfter.wins.toNumber()).to.equal(vaultBefore.wins.toNumber() + 1);
+
+      const market = await program.account.market.fetch(disputeMarketPda);
+      expect(market.totalPaidOut.toNumber()).to.equal(5_000_000);
+      await program.methods.auditMarket().accounts({ market: disputeMarketPda }).rpc();
+    });
+  });
+
+  describe("Oracle Stakes", () => {
+    const oracleMarketId = "omkt01";
+    const shortSeller = Keypair.generate();
+    let oracleMarketPda;
+    let user1OracleStakePda;
+    let shortVaultPda;
+    let shortOracleStakePda;
+
+    before(async () => {
+      [oracleMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(oracleMarketId)],
+        program.programId
+      );
+      [user1OracleStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_stake"), oracleMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [shortVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), shortSeller.publicKey.toBuffer()],
+        program.programId
+      );
+      [shortOracleStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_stake"), oracleMarketPda.toBuffer(), shortSeller.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(shortSeller.publicKey, LAMPORTS_PER_SOL)
+      );
+      await program.methods
+        .initializeReputationVault()
+        .accounts({
+          reputationVault: shortVaultPda,
+          owner: shortSeller.publicKey,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      // Need user1 vault (already exists from previous test)
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Create a new market for oracle stake testing
+      await program.methods
+        .initializeMarket(tweetUrl, oracleMarketId, null)
+        .accounts({
+          market: oracleMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+    });
+
+    it("Stakes on oracle for a market", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      const stakeAmount = new anchor.BN(10_000_000); // 10 Cred
+
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      const balanceBefore = vaultBefore.credBalance.toNumber();
+
+      await program.methods
+        .stakeOnOracle(stakeAmount, true)
+        .accounts({
+          market: oracleMarketPda,
+          reputationVault: user1VaultPda,
+          oracleStake: user1OracleStakePda,
+          user: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore - 10_000_000);
+
+      const oracleStake = await program.account.oracleStake.fetch(user1OracleStakePda);
+      expect(oracleStake.amount.toNumber()).to.equal(10_000_000);
+      expect(oracleStake.backsOracle).to.equal(true);
+      expect(oracleStake.claimed).to.equal(false);
+      expect(oracleStake.user.toBase58()).to.equal(user1.publicKey.toBase58());
+    });
+
+    it("Takes a short position against the oracle", async () => {
+      await program.methods
+        .stakeOnOracle(new anchor.BN(5_000_000), false)
+        .accounts({
+          market: oracleMarketPda,
+          reputationVault: shortVaultPda,
+          oracleStake: shortOracleStakePda,
+          user: shortSeller.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([shortSeller])
+        .rpc();
+
+      const oracleStake = await program.account.oracleStake.fetch(shortOracleStakePda);
+      expect(oracleStake.backsOracle).to.equal(false);
+
+      // Both sides pool on the market, giving an implied 10 / 15 chance the agent is right
+      const market = await program.account.market.fetch(oracleMarketPda);
+      expect(market.oracleForStake.toNumber()).to.equal(10_000_000);
+      expect(market.oracleAgainstStake.toNumber()).to.equal(5_000_000);
+    });
+
+    it("Rejects oracle stake on resolved market", async () => {
+      // Resolve the market first
+      const ipfsHash = new Array(32).fill(99);
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
+        .accounts({
+          market: oracleMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      // Try to stake on resolved market with user2
+      const [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const [user2OracleStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_stake"), oracleMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .stakeOnOracle(new anchor.BN(10_000_000), true)
+          .accounts({
+            market: oracleMarketPda,
+            reputationVault: user2VaultPda,
+            oracleStake: user2OracleStakePda,
+            user: user2.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err) {
+        expect(err.message).to.include("MarketNotOpen");
+      }
+    });
+
+    it("Resolves oracle stakes after market resolution", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+
+      // Still inside the dispute window, so the market is not final yet
+      try {
+        await program.methods
+          .resolveOracleStake()
+          .accounts({
+            market: oracleMarketPda,
+            oracleStake: user1OracleStakePda,
+            reputationVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotFinal");
+      }
+
+      // A dispute that is upheld makes the resolution final
+      await program.methods
+        .disputeMarket()
+        .accounts({
+          market: oracleMarketPda,
+          disputer: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+      await program.methods
+        .settleDispute(true, null)
+        .accounts({
+          market: oracleMarketPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      const balanceBefore = vaultBefore.credBalance.toNumber();
+      const poolBefore = await program.account.insightPool.fetch(insightPoolPda);
+
+      // Resolution was upheld, so the backers take the whole pool; anyone can settle
+      await program.methods
+        .resolveOracleStake()
+        .accounts({
+          market: oracleMarketPda,
+          oracleStake: user1OracleStakePda,
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      // Sole backer wins the shorts' 5 Cred less the 5% house edge
+      expect(vaultAfter.credBalance.toNumber()).to.equal(balanceBefore + 14_750_000);
+
+      const poolAfter = await program.account.insightPool.fetch(insightPoolPda);
+      expect(poolAfter.houseEdgeCollected.toNumber() - poolBefore.houseEdgeCollected.toNumber()).to.equal(250_000);
+      expect(poolAfter.remainingBudget.toNumber() - poolBefore.remainingBudget.toNumber()).to.equal(250_000);
+
+      const oracleStake = await program.account.oracleStake.fetch(user1OracleStakePda);
+      expect(oracleStake.claimed).to.equal(true);
+
+      // The short loses its stake
+      const shortBefore = await program.account.reputationVault.fetch(shortVaultPda);
+      await program.methods
+        .resolveOracleStake()
+        .accounts({
+          market: oracleMarketPda,
+          oracleStake: shortOracleStakePda,
+          reputationVault: shortVaultPda,
+        })
+        .rpc();
+      const shortAfter = await program.account.reputationVault.fetch(shortVaultPda);
+      // Settling still counts as participation, so only a grant tranche unlocks
+      const unlocked = shortBefore.lockedGrant.toNumber() - shortAfter.lockedGrant.toNumber();
+      expect(shortAfter.credBalance.toNumber()).to.equal(shortBefore.credBalance.toNumber() + unlocked);
+    });
+
+    it("Prices an uncontested side from the executor's record", async () => {
+      const pricedMarketId = "omkt02";
+      const [pricedMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(pricedMarketId)],
+        program.programId
+      );
+      const [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      const [user2OracleStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_stake"), pricedMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMarket(tweetUrl, pricedMarketId, null)
+        .accounts({
+          market: pricedMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      await program.methods
+        .stakeOnOracle(new anchor.BN(4_000_000), true)
+        .accounts({
+          market: pricedMarketPda,
+          reputationVault: user2VaultPda,
+          oracleStake: user2OracleStakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      const ipfsHash = new Array(32).fill(7);
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
+        .accounts({
+          market: pricedMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+      await program.methods
+        .disputeMarket()
+        .accounts({
+          market: pricedMarketPda,
+          disputer: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .settleDispute(true, null)
+        .accounts({
+          market: pricedMarketPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      // Fair odds come from the Laplace-smoothed upheld rate, less the 5% house edge
+      const executor = await program.account.agentExecutor.fetch(agentExecutorPda);
+      const upheld = executor.resolutionsUpheld.toNumber();
+      const overturned = executor.resolutionsOverturned.toNumber();
+      const upheldBps = Math.floor(((upheld + 1) * 10_000) / (upheld + overturned + 2));
+      const winnings = Math.floor((4_000_000 * (10_000 - upheldBps)) / upheldBps);
+      const houseFee = Math.floor((winnings * 500) / 10_000);
+
+      const vaultBefore = await program.account.reputationVault.fetch(user2VaultPda);
+      await program.methods
+        .resolveOracleStake()
+        .accounts({
+          market: pricedMarketPda,
+          oracleStake: user2OracleStakePda,
+          reputationVault: user2VaultPda,
+        })
+        .rpc();
+      const vaultAfter = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(
+        4_000_000 + winnings - houseFee
+      );
+    });
+
+    it("Scales oracle winnings down on a low-confidence resolution", async () => {
+      const [lowMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("omkt03")],
+        program.programId
+      );
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const oracleStakeOf = (user: Keypair) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("oracle_stake"), lowMarketPda.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0];
+
+      await program.methods
+        .initializeMarket(tweetUrl, "omkt03", null)
+        .accounts({
+          market: lowMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      for (const [user, vault, amount, backs] of [
+        [user1, user1VaultPda, 4_000_000, true],
+        [shortSeller, shortVaultPda, 2_000_000, false],
+      ] as [Keypair, PublicKey, number, boolean][]) {
+        await program.methods
+          .stakeOnOracle(new anchor.BN(amount), backs)
+          .accounts({
+            market: lowMarketPda,
+            reputationVault: vault,
+            oracleStake: oracleStakeOf(user),
+            user: user.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+      }
+
+      // Half the full-reward threshold of 70%
+      const ipfsHash = new Array(32).fill(8);
+      await program.methods
+        .resolveMarket(1, ipfsHash, 3500, "test-model", ipfsHash, ipfsHash, null)
+        .accounts({
+          market: lowMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+      await program.methods
+        .disputeMarket()
+        .accounts({ market: lowMarketPda, disputer: user2.publicKey })
+        .signers([user2])
+        .rpc();
+      await program.methods
+        .settleDispute(true, null)
+        .accounts({ market: lowMarketPda, authority: provider.wallet.publicKey })
+        .rpc();
+
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      const poolBefore = await program.account.insightPool.fetch(insightPoolPda);
+      await program.methods
+        .resolveOracleStake()
+        .accounts({
+          market: lowMarketPda,
+          oracleStake: oracleStakeOf(user1),
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      const poolAfter = await program.account.insightPool.fetch(insightPoolPda);
+
+      // 2 Cred winnings less the 5% edge leaves 1.9 Cred, of which half is paid
+      const unlocked = vaultBefore.lockedGrant.toNumber() - vaultAfter.lockedGrant.toNumber();
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(
+        4_000_000 + 950_000 + unlocked
+      );
+      expect(poolAfter.houseEdgeCollected.toNumber() - poolBefore.houseEdgeCollected.toNumber()).to.equal(100_000);
+      expect(poolAfter.remainingBudget.toNumber() - poolBefore.remainingBudget.toNumber()).to.equal(
+        100_000 + 950_000
+      );
+    });
+  });
+
+  describe("Invalid Resolution", () => {
+    const invalidMarketId = "imkt01";
+    let invalidMarketPda;
+    let user2VaultPda;
+    let user2StakePda;
+
+    before(async () => {
+      [invalidMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(invalidMarketId)],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), invalidMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMarket(tweetUrl, invalidMarketId, null)
+        .accounts({
+          market: invalidMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+
+      await program.methods
+        .stakeCred(false, new anchor.BN(30_000_000))
+        .accounts({
+          market: invalidMarketPda,
+          reputationVault: user2VaultPda,
+          credStake: user2StakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      const ipfsHash = new Array(32).fill(7);
+      await program.methods
+        .resolveMarket(2, ipfsHash, 4000, "test-model", ipfsHash, ipfsHash, null) // Invalid
+        .accounts({
+          market: invalidMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      // No more disputes from here on: resolutions are final at once
+      await program.methods
+        .updateProtocolConfig({ disputeWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    it("Refunds Cred stakes at par on an invalid market", async () => {
+      const vaultBefore = await program.account.reputationVault.fetch(user2VaultPda);
+
+      await program.methods
+        .refundCredStake()
+        .accounts({
+          market: invalidMarketPda,
+          credStake: user2StakePda,
+          reputationVault: user2VaultPda,
+        })
+        .rpc();
+
+      const vaultAfter = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(vaultBefore.credBalance.toNumber() + 30_000_000);
+
+      const stake = await program.account.credStake.fetch(user2StakePda);
+      expect(stake.claimed).to.equal(true);
+    });
+
+    it("Cannot refund the same stake twice", async () => {
+      try {
+        await program.methods
+          .refundCredStake()
+          .accounts({
+            market: invalidMarketPda,
+            credStake: user2StakePda,
+            reputationVault: user2VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err) {
+        expect(err.message).to.include("StakeAlreadyClaimed");
+      }
+    });
+
+    it("Audits the refunded market's Cred flows", async () => {
+      await program.methods.auditMarket().accounts({ market: invalidMarketPda }).rpc();
+
+      const market = await program.account.market.fetch(invalidMarketPda);
+      expect(market.totalRefunded.toNumber()).to.equal(30_000_000);
+      expect(market.totalPaidOut.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Leaderboard", () => {
+    let leaderboardPda;
+    let user1VaultPda;
+
+    before(async () => {
+      [leaderboardPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), Buffer.from([0])], // LeaderboardMetric::NetCredEarned
+        program.programId
+      );
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Initializes a net Cred leaderboard", async () => {
+      await program.methods
+        .initializeLeaderboard({ netCredEarned: {} })
+        .accounts({
+          leaderboard: leaderboardPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+      expect(leaderboard.metric).to.deep.equal({ netCredEarned: {} });
+      expect(leaderboard.entries.length).to.equal(0);
+    });
+
+    it("Submits a vault to the leaderboard", async () => {
+      await program.methods
+        .submitToLeaderboard()
+        .accounts({
+          leaderboard: leaderboardPda,
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+
+      const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+      expect(leaderboard.entries.length).to.equal(1);
+      expect(leaderboard.entries[0].owner.toBase58()).to.equal(user1.publicKey.toBase58());
+      expect(leaderboard.entries[0].score.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Seasons", () => {
+    let season1Pda;
+    let season2Pda;
+    let user1VaultPda;
+    let user1StatsPda;
+
+    const seasonPda = (n) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("season"), new anchor.BN(n).toArrayLike(Buffer, "le", 4)],
+        program.programId
+      )[0];
+
+    before(async () => {
+      season1Pda = seasonPda(1);
+      season2Pda = seasonPda(2);
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user1StatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("season_stats"), season1Pda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Opens the first season", async () => {
+      await program.methods
+        .initializeSeason(new anchor.BN(2)) // 2 second season
+        .accounts({
+          season: season1Pda,
+          protocolConfig: protocolConfigPda,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const season = await program.account.season.fetch(season1Pda);
+      expect(season.number).to.equal(1);
+      expect(season.status).to.deep.equal({ active: {} });
+
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+      expect(config.currentSeason).to.equal(1);
+    });
+
+    it("Joins the active season", async () => {
+      await program.methods
+        .joinSeason()
+        .accounts({
+          season: season1Pda,
+          seasonStats: user1StatsPda,
+          reputationVault: user1VaultPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const stats = await program.account.seasonStats.fetch(user1StatsPda);
+      expect(stats.owner.toBase58()).to.equal(user1.publicKey.toBase58());
+      expect(stats.settledCount.toNumber()).to.equal(0);
+    });
+
+    it("Ends the season and opens the next one", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+
+      await program.methods
+        .endSeason()
+        .accounts({
+          currentSeason: season1Pda,
+          nextSeason: season2Pda,
+          protocolConfig: protocolConfigPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const ended = await program.account.season.fetch(season1Pda);
+      expect(ended.status).to.deep.equal({ ended: {} });
+
+      const next = await program.account.season.fetch(season2Pda);
+      expect(next.number).to.equal(2);
+      expect(next.status).to.deep.equal({ active: {} });
+    });
+  });
+
+
+  describe("Cred Transfers", () => {
+    let marketPda;
+    let user1VaultPda;
+    let user2VaultPda;
+
+    before(async () => {
+      [marketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(marketId)],
+        program.programId
+      );
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Tips an evidence submitter", async () => {
+      const [evidencePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("evidence"), marketPda.toBuffer(), Buffer.from([1])],
+        program.programId
+      );
+      const submitterBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      await program.methods
+        .tipEvidence(1, new anchor.BN(5_000_000), "great find")
+        .accounts({
+          market: marketPda,
+          evidence: evidencePda,
+          senderVault: user2VaultPda,
+          submitterVault: user1VaultPda,
+          sender: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      const submitterAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(submitterAfter.credBalance.toNumber()).to.equal(submitterBefore.credBalance.toNumber() + 5_000_000);
+      expect(submitterAfter.totalEarned.toNumber()).to.equal(submitterBefore.totalEarned.toNumber());
+
+      const evidence = await program.account.evidence.fetch(evidencePda);
+      expect(evidence.tipsReceived.toNumber()).to.equal(5_000_000);
+    });
+
+    it("Transfers Cred between vaults and enforces the daily limit", async () => {
+      await program.methods
+        .transferCred(new anchor.BN(10_000_000), null)
+        .accounts({
+          senderVault: user2VaultPda,
+          recipientVault: user1VaultPda,
+          sender: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      const sender = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(sender.transferredToday.toNumber()).to.equal(15_000_000); // Tip + transfer
+
+      // Default limit is 50 Cred per day
+      try {
+        await program.methods
+          .transferCred(new anchor.BN(40_000_000), null)
+          .accounts({
+            senderVault: user2VaultPda,
+            recipientVault: user1VaultPda,
+            sender: user2.publicKey,
+          })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("DailyTransferLimitExceeded");
+      }
+    });
+  });
+
+
+  describe("Referrals", () => {
+    const referee = Keypair.generate();
+    const referralMarketId = "rmkt01";
+    let user1VaultPda;
+    let refereeVaultPda;
+    let referralMarketPda;
+
+    const ipfsHash = new Array(32).fill(13);
+
+    before(async () => {
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [refereeVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), referee.publicKey.toBuffer()],
+        program.programId
+      );
+      [referralMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(referralMarketId)],
+        program.programId
+      );
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(referee.publicKey, LAMPORTS_PER_SOL)
+      );
+    });
+
+    const createRefereeVault = () =>
+      program.methods.initializeReputationVault().accounts({
+        reputationVault: refereeVaultPda,
+        owner: referee.publicKey,
+        payer: provider.wallet.publicKey,
+        referrerVault: user1VaultPda,
+        systemProgram: SystemProgram.programId,
+      });
+
+    it("Rejects a referral the vault owner did not sign", async () => {
+      const referrerBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      try {
+        await createRefereeVault().rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ReferralRequiresSignature");
+      }
+
+      const referrerAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(referrerAfter.referralCount).to.equal(referrerBefore.referralCount);
+    });
+
+    it("Records the referrer when a vault is created", async () => {
+      const referrerBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      await createRefereeVault().signers([referee]).rpc();
+
+      const vault = await program.account.reputationVault.fetch(refereeVaultPda);
+      expect(vault.referrer.toBase58()).to.equal(user1.publicKey.toBase58());
+      expect(vault.referralBonusPaid).to.equal(false);
+
+      // No attestation: reduced grant, rest locked
+      expect(vault.credBalance.toNumber()).to.equal(20_000_000);
+      expect(vault.lockedGrant.toNumber()).to.equal(80_000_000);
+
+      const referrerAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(referrerAfter.referralCount).to.equal(referrerBefore.referralCount + 1);
+    });
+
+    it("Does not pay the bonus before the referee settles a stake", async () => {
+      try {
+        await program.methods
+          .claimReferralBonus()
+          .accounts({
+            refereeVault: refereeVaultPda,
+            referrerVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ReferralBonusNotDue");
+      }
+    });
+
+    it("Pays the referrer's bonus once the referee has settled the minimum stake", async () => {
+      const [refereeStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), referralMarketPda.toBuffer(), referee.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMarket(tweetUrl, referralMarketId, null)
+        .accounts({
+          market: referralMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .stakeCred(true, new anchor.BN(1_000_000))
+        .accounts({
+          market: referralMarketPda,
+          reputationVault: refereeVaultPda,
+          credStake: refereeStakePda,
+          user: referee.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([referee])
+        .rpc();
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES
+        .accounts({
+          market: referralMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+      const referrerBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      await program.methods
+        .claimCredStake()
+        .accounts({
+          market: referralMarketPda,
+          credStake: refereeStakePda,
+          reputationVault: refereeVaultPda,
+          referrerVault: user1VaultPda,
+        })
+        .rpc();
+
+      // A 1 Cred stake is below the referral minimum, so no bonus yet
+      let vault = await program.account.reputationVault.fetch(refereeVaultPda);
+      expect(config.referralMinStake.toNumber()).to.be.greaterThan(1_000_000);
+      expect(vault.settledCount.toNumber()).to.equal(1);
+      expect(vault.settledStake.toNumber()).to.equal(1_000_000);
+      expect(vault.referralBonusPaid).to.equal(false);
+      expect(
+        (await program.account.reputationVault.fetch(user1VaultPda)).credBalance.toNumber()
+      ).to.equal(referrerBefore.credBalance.toNumber());
+      try {
+        await program.methods
+          .claimReferralBonus()
+          .accounts({
+            refereeVault: refereeVaultPda,
+            referrerVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ReferralBonusNotDue");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ referralMinStake: new anchor.BN(1_000_000) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await program.methods
+        .claimReferralBonus()
+        .accounts({
+          refereeVault: refereeVaultPda,
+          referrerVault: user1VaultPda,
+        })
+        .rpc();
+      await program.methods
+        .updateProtocolConfig({ referralMinStake: config.referralMinStake })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      const referrerAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(referrerAfter.credBalance.toNumber()).to.equal(
+        referrerBefore.credBalance.toNumber() + config.referralBonus.toNumber()
+      );
+      vault = await program.account.reputationVault.fetch(refereeVaultPda);
+      expect(vault.referralBonusPaid).to.equal(true);
+
+      // A 1 Cred stake unlocks at most 1 Cred of the locked grant
+      expect(vault.lockedGrant.toNumber()).to.equal(79_000_000);
+
+      // The bonus is paid once
+      try {
+        await program.methods
+          .claimReferralBonus()
+          .accounts({
+            refereeVault: refereeVaultPda,
+            referrerVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ReferralBonusNotDue");
+      }
+    });
+  });
+
+
+  describe("Identity Verification", () => {
+    const subject = Keypair.generate();
+    let user1VaultPda;
+    let attestationPda;
+    let attesterPda;
+    let subjectAttestationPda;
+
+    before(async () => {
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [attestationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), provider.wallet.publicKey.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [attesterPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attester"), provider.wallet.publicKey.toBuffer()],
+        program.programId
+      );
+      [subjectAttestationPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("attestation"), provider.wallet.publicKey.toBuffer(), subject.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Awards IdentityVerification Cred exactly once", async () => {
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      await program.methods
+        .claimIdentityReward()
+        .accounts({
+          attestation: attestationPda,
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(vaultAfter.credBalance.toNumber()).to.equal(vaultBefore.credBalance.toNumber() + 25_000_000);
+
+      try {
+        await program.methods
+          .claimIdentityReward()
+          .accounts({
+            attestation: attestationPda,
+            reputationVault: user1VaultPda,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("IdentityRewardAlreadyClaimed");
+      }
+    });
+
+    it("Renews an attestation's expiry", async () => {
+      await program.methods
+        .issueAttestation({ twitterHandle: {} }, Array.from(Buffer.alloc(32, 3)), new anchor.BN(0))
+        .accounts({
+          attestation: subjectAttestationPda,
+          subject: subject.publicKey,
+          attesterAccount: attesterPda,
+          attester: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const expiresAt = Math.floor(Date.now() / 1000) + 86_400;
+      await program.methods
+        .renewAttestation(new anchor.BN(expiresAt))
+        .accounts({
+          attestation: subjectAttestationPda,
+          attesterAccount: attesterPda,
+          attester: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const attestation = await program.account.attestation.fetch(subjectAttestationPda);
+      expect(attestation.expiresAt.toNumber()).to.equal(expiresAt);
+
+      try {
+        await program.methods
+          .renewAttestation(new anchor.BN(1))
+          .accounts({
+            attestation: subjectAttestationPda,
+            attesterAccount: attesterPda,
+            attester: provider.wallet.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidAttestationExpiry");
+      }
+    });
+
+    it("Only lets the issuing attester revoke an attestation", async () => {
+      try {
+        await program.methods
+          .revokeAttestation()
+          .accounts({ attestation: subjectAttestationPda, attester: user2.publicKey })
+          .signers([user2])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ConstraintSeeds");
+      }
+
+      await program.methods
+        .revokeAttestation()
+        .accounts({ attestation: subjectAttestationPda, attester: provider.wallet.publicKey })
+        .rpc();
+
+      expect(await program.account.attestation.fetchNullable(subjectAttestationPda)).to.equal(null);
+    });
+  });
+
+
+  describe("Cred Faucet", () => {
+    const faucetUser = Keypair.generate();
+    let faucetPda;
+    let vaultPda;
+
+    before(async () => {
+      [faucetPda] = PublicKey.findProgramAddressSync([Buffer.from("faucet")], program.programId);
+      [vaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), faucetUser.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeFaucet()
+        .accounts({
+          faucet: faucetPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      // Unattested vault starts with the reduced 20 Cred grant, under the faucet threshold
+      await program.methods
+        .initializeReputationVault()
+        .accounts({
+          reputationVault: vaultPda,
+          owner: faucetUser.publicKey,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+    });
+
+    it("Pays a low vault once per cooldown", async () => {
+      await program.methods
+        .claimFaucet()
+        .accounts({
+          faucet: faucetPda,
+          reputationVault: vaultPda,
+          user: faucetUser.publicKey,
+        })
+        .signers([faucetUser])
+        .rpc();
+
+      const vault = await program.account.reputationVault.fetch(vaultPda);
+      expect(vault.credBalance.toNumber()).to.equal(45_000_000);
+      expect(vault.lastFaucetClaim.toNumber()).to.be.greaterThan(0);
+
+      const faucet = await program.account.faucet.fetch(faucetPda);
+      expect(faucet.distributedInEpoch.toNumber()).to.equal(25_000_000);
+
+      try {
+        await program.methods
+          .claimFaucet()
+          .accounts({
+            faucet: faucetPda,
+            reputationVault: vaultPda,
+            user: faucetUser.publicKey,
+          })
+          .signers([faucetUser])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("FaucetCooldown");
+      }
+    });
+
+    it("Keeps faucet Cred out of transfers", async () => {
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      let vault = await program.account.reputationVault.fetch(vaultPda);
+      expect(vault.faucetBalance.toNumber()).to.equal(25_000_000);
+
+      // Only the 20 Cred grant can leave the vault
+      try {
+        await program.methods
+          .transferCred(new anchor.BN(21_000_000), null)
+          .accounts({
+            senderVault: vaultPda,
+            recipientVault: user1VaultPda,
+            sender: faucetUser.publicKey,
+          })
+          .signers([faucetUser])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("FaucetCredNotTransferable");
+      }
+
+      await program.methods
+        .transferCred(new anchor.BN(20_000_000), null)
+        .accounts({
+          senderVault: vaultPda,
+          recipientVault: user1VaultPda,
+          sender: faucetUser.publicKey,
+        })
+        .signers([faucetUser])
+        .rpc();
+
+      vault = await program.account.reputationVault.fetch(vaultPda);
+      expect(vault.credBalance.toNumber()).to.equal(25_000_000);
+      expect(vault.faucetBalance.toNumber()).to.equal(25_000_000);
+    });
+  });
+
+  describe("Executor Registry", () => {
+    const curatedResolver = Keypair.generate();
+    const registryMarketId = "xmkt01";
+    let curatedExecutorPda;
+    let registryMarketPda;
+
+    before(async () => {
+      [curatedExecutorPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("agent_executor"), curatedResolver.publicKey.toBuffer()],
+        program.programId
+      );
+      [registryMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(registryMarketId)],
+        program.programId
+      );
+    });
+
+    it("Registers an additional executor", async () => {
+      await program.methods
+        .registerExecutor(curatedResolver.publicKey, "https://prophecy.example/resolvers/curated.json")
+        .accounts({
+          agentExecutor: curatedExecutorPda,
+          configAuthority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const executor = await program.account.agentExecutor.fetch(curatedExecutorPda);
+      expect(executor.authority.toBase58()).to.equal(curatedResolver.publicKey.toBase58());
+      expect(executor.active).to.equal(true);
+      expect(executor.metadataUri).to.equal("https://prophecy.example/resolvers/curated.json");
+    });
+
+    it("Resolves a market only through the executor its creator chose", async () => {
+      await program.methods
+        .initializeMarket(tweetUrl, registryMarketId, null)
+        .accounts({
+          market: registryMarketPda,
+          agentExecutor: curatedExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+
+      const market = await program.account.market.fetch(registryMarketPda);
+      expect(market.agentExecutor.toBase58()).to.equal(curatedExecutorPda.toBase58());
+
+      // The default executor is not this market's resolver
+      const ipfsHash = new Array(32).fill(5);
+      try {
+        await program.methods
+          .resolveMarket(0, ipfsHash, 8000, "curated", ipfsHash, ipfsHash, null)
+          .accounts({
+            market: registryMarketPda,
+            agentExecutor: agentExecutorPda,
+            authority: agentExecutorAuthority.publicKey,
+          })
+          .signers([agentExecutorAuthority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("WrongExecutor");
+      }
+
+      await program.methods
+        .resolveMarket(0, ipfsHash, 8000, "curated", ipfsHash, ipfsHash, null)
+        .accounts({
+          market: registryMarketPda,
+          agentExecutor: curatedExecutorPda,
+          authority: curatedResolver.publicKey,
+        })
+        .signers([curatedResolver])
+        .rpc();
+
+      const executor = await program.account.agentExecutor.fetch(curatedExecutorPda);
+      expect(executor.marketsResolved.toNumber()).to.equal(1);
+    });
+
+    it("Rejects new markets for a deactivated executor", async () => {
+      await program.methods
+        .setExecutorActive(false)
+        .accounts({
+          agentExecutor: curatedExecutorPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      const [inactiveMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("xmkt02")],
+        program.programId
+      );
+      try {
+        await program.methods
+          .initializeMarket(tweetUrl, "xmkt02", null)
+          .accounts({
+            market: inactiveMarketPda,
+            agentExecutor: curatedExecutorPda,
+            creator: marketCreator.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([marketCreator])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ExecutorInactive");
+      }
+    });
+  });
+
+  describe("Resolver Ensemble", () => {
+    const resolverA = Keypair.generate();
+    const resolverB = Keypair.generate();
+    let executorAPda;
+    let executorBPda;
+
+    const verdictPda = (marketPda: PublicKey, executorPda: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("verdict"), marketPda.toBuffer(), executorPda.toBuffer()],
+        program.programId
+      )[0];
+
+    const executorAccounts = (executors: PublicKey[]) =>
+      executors.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }));
+
+    const createEnsembleMarket = async (id: string, quorum: number, executors: PublicKey[]) => {
+      const [marketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(id)],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, id, null)
+        .accounts({
+          market: marketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      await program.methods
+        .enableEnsemble(quorum, executors)
+        .accounts({ market: marketPda, creator: marketCreator.publicKey })
+        .remainingAccounts(executorAccounts(executors))
+        .signers([marketCreator])
+        .rpc();
+      return marketPda;
+    };
+
+    const submitVerdict = async (
+      marketPda: PublicKey,
+      executorPda: PublicKey,
+      resolver: Keypair,
+      outcome: number
+    ) => {
+      const ipfsHash = new Array(32).fill(outcome + 10);
+      await program.methods
+        .submitVerdict(outcome, ipfsHash, 8000)
+        .accounts({
+          market: marketPda,
+          verdict: verdictPda(marketPda, executorPda),
+          agentExecutor: executorPda,
+          authority: resolver.publicKey,
+        })
+        .signers([resolver])
+        .rpc();
+    };
+
+    before(async () => {
+      for (const resolver of [resolverA, resolverB]) {
+        await provider.connection.confirmTransaction(
+          await provider.connection.requestAirdrop(resolver.publicKey, LAMPORTS_PER_SOL)
+        );
+        await program.methods
+          .registerExecutor(resolver.publicKey, "")
+          .accounts({ configAuthority: provider.wallet.publicKey })
+          .rpc();
+      }
+      [executorAPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("agent_executor"), resolverA.publicKey.toBuffer()],
+        program.programId
+      );
+      [executorBPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("agent_executor"), resolverB.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Resolves once a quorum of executors agrees", async () => {
+      const marketPda = await createEnsembleMarket("emkt01", 2, [executorAPda, executorBPda, agentExecutorPda]);
+
+      // A single executor can no longer resolve the market on its own
+      const ipfsHash = new Array(32).fill(3);
+      try {
+        await program.methods
+          .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null)
+          .accounts({
+            market: marketPda,
+            agentExecutor: agentExecutorPda,
+            authority: agentExecutorAuthority.publicKey,
+          })
+          .signers([agentExecutorAuthority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("EnsembleMarket");
+      }
+
+      await submitVerdict(marketPda, executorAPda, resolverA, 1);
+      let market = await program.account.market.fetch(marketPda);
+      expect(market.status).to.deep.equal({ open: {} });
+
+      await submitVerdict(marketPda, executorBPda, resolverB, 1);
+      market = await program.account.market.fetch(marketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.equal(1);
+      expect(market.modelId).to.equal("ensemble");
+      expect(market.verdictTallies).to.deep.equal([0, 2, 0]);
+
+      const verdict = await program.account.executorVerdict.fetch(verdictPda(marketPda, executorAPda));
+      expect(verdict.outcome).to.equal(1);
+    });
+
+    it("Only accepts verdicts from the market's executors", async () => {
+      const marketPda = await createEnsembleMarket("emkt03", 2, [executorAPda, agentExecutorPda]);
+
+      try {
+        await submitVerdict(marketPda, executorBPda, resolverB, 1);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ExecutorNotInEnsemble");
+      }
+
+      // Duplicate executors cannot pad the ensemble
+      const [duplicatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("emkt04")],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, "emkt04", null)
+        .accounts({
+          market: duplicatePda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      try {
+        await program.methods
+          .enableEnsemble(2, [executorAPda, executorAPda])
+          .accounts({ market: duplicatePda, creator: marketCreator.publicKey })
+          .remainingAccounts(executorAccounts([executorAPda, executorAPda]))
+          .signers([marketCreator])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidEnsemble");
+      }
+    });
+
+    it("Only enables bonded, registered executors before any Cred is staked", async () => {
+      const [marketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("emkt05")],
+        program.programId
+      );
+      const [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      const [credStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), marketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, "emkt05", null)
+        .accounts({
+          market: marketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      const enable = (executors: PublicKey[], accounts: PublicKey[] = executors) =>
+        program.methods
+          .enableEnsemble(2, executors)
+          .accounts({ market: marketPda, creator: marketCreator.publicKey })
+          .remainingAccounts(executorAccounts(accounts))
+          .signers([marketCreator])
+          .rpc();
+
+      // Executors A and B have no bond
+      await program.methods
+        .updateProtocolConfig({ executorMinBond: new anchor.BN(1) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      try {
+        await enable([executorAPda, executorBPda]);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ExecutorBondTooLow");
+      }
+      await program.methods
+        .updateProtocolConfig({ executorMinBond: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      // Any listed key must be a registered executor
+      const stranger = Keypair.generate().publicKey;
+      try {
+        await enable([executorAPda, stranger]);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("AccountNotInitialized");
+      }
+      try {
+        await enable([executorAPda, executorBPda], [executorAPda, agentExecutorPda]);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidEnsemble");
+      }
+
+      // Stakers committed to the single executor cannot be moved onto an ensemble
+      await program.methods
+        .stakeCred(true, new anchor.BN(1_000_000))
+        .accounts({
+          market: marketPda,
+          reputationVault: user1VaultPda,
+          credStake: credStakePda,
+          user: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      try {
+        await enable([executorAPda, executorBPda]);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("EnsembleAfterStakes");
+      }
+    });
+
+    it("Escalates a split ensemble into the dispute flow", async () => {
+      const marketPda = await createEnsembleMarket("emkt02", 3, [executorAPda, executorBPda, agentExecutorPda]);
+
+      await submitVerdict(marketPda, executorAPda, resolverA, 1);
+      await submitVerdict(marketPda, executorBPda, resolverB, 0);
+
+      // Three agreeing verdicts are no longer possible; no outcome is picked
+      let market = await program.account.market.fetch(marketPda);
+      expect(market.status).to.deep.equal({ disputed: {} });
+      expect(market.disputeStatus).to.deep.equal({ pending: {} });
+      expect(market.verdictTallies).to.deep.equal([1, 1, 0]);
+      expect(market.outcome).to.be.null;
+
+      // There is no verdict to uphold
+      try {
+        await program.methods
+          .settleDispute(true, null)
+          .accounts({ market: marketPda, authority: provider.wallet.publicKey })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidCorrectedOutcome");
+      }
+
+      await program.methods
+        .settleDispute(false, 1)
+        .accounts({ market: marketPda, authority: provider.wallet.publicKey })
+        .rpc();
+
+      market = await program.account.market.fetch(marketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.disputeStatus).to.deep.equal({ overturned: {} });
+      expect(market.outcome).to.equal(1);
+      expect(market.overturnedOutcome).to.be.null;
+      expect(market.settlementBps).to.equal(10000);
+      expect(market.resolvedAt.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Signed Verdicts", () => {
+    const signedMarketId = "smkt01";
+    let signedMarketPda;
+
+    // VERDICT_DOMAIN followed by the Borsh-encoded SignedVerdict
+    const verdictMessage = (verdict) => {
+      const header = Buffer.alloc(32 + 1 + 32 + 2);
+      verdict.market.toBuffer().copy(header, 0);
+      header.writeUInt8(verdict.outcome, 32);
+      Buffer.from(verdict.ipfsTranscriptHash).copy(header, 33);
+      header.writeUInt16LE(verdict.confidenceBps, 65);
+      const modelId = Buffer.from(verdict.modelId);
+      const modelIdLen = Buffer.alloc(4);
+      modelIdLen.writeUInt32LE(modelId.length);
+      const settlement = Buffer.alloc(verdict.settlementBps === null ? 1 : 3);
+      if (verdict.settlementBps !== null) {
+        settlement.writeUInt8(1, 0);
+        settlement.writeUInt16LE(verdict.settlementBps, 1);
+      }
+      const trailer = Buffer.alloc(8 + 8);
+      trailer.writeBigInt64LE(BigInt(verdict.expiry.toString()), 0);
+      trailer.writeBigUInt64LE(BigInt(verdict.nonce.toString()), 8);
+      return Buffer.concat([
+        Buffer.from("prophecy:verdict:v1"),
+        header,
+        modelIdLen,
+        modelId,
+        Buffer.from(verdict.researcherHash),
+        Buffer.from(verdict.judgeHash),
+        settlement,
+        trailer,
+      ]);
+    };
+
+    const relay = (verdict, signer: Keypair) =>
+      program.methods
+        .resolveMarketSigned(verdict)
+        .accounts({
+          market: verdict.market,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          relayer: user1.publicKey,
+        })
+        .preInstructions([
+          anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message: verdictMessage(verdict),
+          }),
+        ])
+        .signers([user1])
+        .rpc();
+
+    before(async () => {
+      [signedMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(signedMarketId)],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, signedMarketId, null)
+        .accounts({
+          market: signedMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+    });
+
+    it("Lets a relayer submit a verdict signed by the executor key", async () => {
+      const verdict = {
+        market: signedMarketPda,
+        outcome: 1,
+        ipfsTranscriptHash: new Array(32).fill(42),
+        confidenceBps: 8500,
+        modelId: "signed-model",
+        researcherHash: new Array(32).fill(45),
+        judgeHash: new Array(32).fill(46),
+        settlementBps: null,
+        expiry: new anchor.BN(Math.floor(Date.now() / 1000) + 600),
+        nonce: new anchor.BN(1),
+      };
+
+      // A signature from any other key is rejected
+      try {
+        await relay(verdict, user2);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSignedVerdict");
+      }
+
+      await relay(verdict, agentExecutorAuthority);
+
+      const market = await program.account.market.fetch(signedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.equal(1);
+      expect(market.confidenceBps).to.equal(8500);
+      expect(market.modelId).to.equal("signed-model");
+      expect(market.researcherHash).to.deep.equal(new Array(32).fill(45));
+      expect(market.judgeHash).to.deep.equal(new Array(32).fill(46));
+      expect(market.settlementBps).to.equal(10000);
+      expect(market.verdictNonce.toNumber()).to.equal(1);
+    });
+
+    it("Accepts signed verdicts for different markets in any order", async () => {
+      const [replayMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("smkt02")],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, "smkt02", null)
+        .accounts({
+          market: replayMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+
+      // Nonces are scoped to the market, so an earlier-numbered verdict still lands
+      await relay(
+        {
+          market: replayMarketPda,
+          outcome: 0,
+          ipfsTranscriptHash: new Array(32).fill(43),
+          confidenceBps: 8000,
+          modelId: "signed-model",
+          researcherHash: new Array(32).fill(0),
+          judgeHash: new Array(32).fill(0),
+          settlementBps: null,
+          expiry: new anchor.BN(Math.floor(Date.now() / 1000) + 600),
+          nonce: new anchor.BN(1),
+        },
+        agentExecutorAuthority
+      );
+      const market = await program.account.market.fetch(replayMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.outcome).to.equal(0);
+
+      // A resolved market takes no further verdicts, so none can be replayed
+      try {
+        await relay(
+          {
+            market: signedMarketPda,
+            outcome: 0,
+            ipfsTranscriptHash: new Array(32).fill(44),
+            confidenceBps: 8000,
+            modelId: "signed-model",
+            researcherHash: new Array(32).fill(0),
+            judgeHash: new Array(32).fill(0),
+            settlementBps: null,
+            expiry: new anchor.BN(Math.floor(Date.now() / 1000) + 600),
+            nonce: new anchor.BN(2),
+          },
+          agentExecutorAuthority
+        );
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotOpen");
+      }
+    });
+
+    it("Rejects signed verdicts the executor has revoked", async () => {
+      const [revokedMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("smkt03")],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, "smkt03", null)
+        .accounts({
+          market: revokedMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      const verdict = (nonce: number) => ({
+        market: revokedMarketPda,
+        outcome: 1,
+        ipfsTranscriptHash: new Array(32).fill(47),
+        confidenceBps: 9000,
+        modelId: "signed-model",
+        researcherHash: new Array(32).fill(0),
+        judgeHash: new Array(32).fill(0),
+        settlementBps: 6000,
+        expiry: new anchor.BN(Math.floor(Date.now() / 1000) + 600),
+        nonce: new anchor.BN(nonce),
+      });
+
+      // The executor withdraws every verdict it signed up to nonce 5
+      await program.methods
+        .revokeSignedVerdicts(new anchor.BN(5))
+        .accounts({
+          market: revokedMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+      try {
+        await relay(verdict(5), agentExecutorAuthority);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("StaleVerdictNonce");
+      }
+
+      await relay(verdict(6), agentExecutorAuthority);
+      const market = await program.account.market.fetch(revokedMarketPda);
+      expect(market.status).to.deep.equal({ resolved: {} });
+      expect(market.settlementBps).to.equal(6000);
+      expect(market.verdictNonce.toNumber()).to.equal(6);
+    });
+  });
+
+  describe("Market Expiry", () => {
+    const expiryMarketId = "xmkt01";
+    let expiryMarketPda;
+    let user1StakePda;
+    let user2OracleStakePda;
+    let user1VaultPda;
+    let user2VaultPda;
+
+    before(async () => {
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      [expiryMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(expiryMarketId)],
+        program.programId
+      );
+      [user1StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), expiryMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2OracleStakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_stake"), expiryMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMarket(tweetUrl, expiryMarketId, null)
+        .accounts({
+          market: expiryMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+
+      await program.methods
+        .stakeCred(true, new anchor.BN(5_000_000))
+        .accounts({
+          market: expiryMarketPda,
+          reputationVault: user1VaultPda,
+          credStake: user1StakePda,
+          user: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+
+      await program.methods
+        .stakeOnOracle(new anchor.BN(2_000_000), true)
+        .accounts({
+          market: expiryMarketPda,
+          reputationVault: user2VaultPda,
+          oracleStake: user2OracleStakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+    });
+
+    const expire = () =>
+      program.methods
+        .expireMarket()
+        .accounts({ market: expiryMarketPda, caller: user1.publicKey })
+        .signers([user1])
+        .rpc();
+
+    it("Cannot expire a market before its maximum age", async () => {
+      try {
+        await expire();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotExpired");
+      }
+    });
+
+    it("Voids an unresolved market past its maximum age and pays the caller", async () => {
+      await program.methods
+        .updateProtocolConfig({ marketMaxAge: new anchor.BN(1) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      // The crank reward counts against the daily mint ceiling
+      const configBefore = await program.account.protocolConfig.fetch(protocolConfigPda);
+      await program.methods
+        .updateProtocolConfig({ dailyMintCeiling: new anchor.BN(999_999) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      try {
+        await expire();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("DailyMintCeilingExceeded");
+      }
+      await program.methods
+        .updateProtocolConfig({ dailyMintCeiling: configBefore.dailyMintCeiling })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      const callerBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      await expire();
+
+      const configAfter = await program.account.protocolConfig.fetch(protocolConfigPda);
+      const mintedBefore = configAfter.mintDay.eq(configBefore.mintDay) ? configBefore.mintedToday.toNumber() : 0;
+      expect(configAfter.mintedToday.toNumber()).to.equal(mintedBefore + 1_000_000);
+
+      const market = await program.account.market.fetch(expiryMarketPda);
+      expect(market.status).to.deep.equal({ voided: {} });
+      expect(market.outcome).to.equal(2);
+
+      const callerAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(callerAfter.credBalance.toNumber()).to.equal(callerBefore.credBalance.toNumber() + 1_000_000);
+
+      // A voided market cannot be expired again
+      try {
+        await expire();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotOpen");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ marketMaxAge: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    it("Refunds Cred and Oracle stakes on the voided market", async () => {
+      const user1Before = await program.account.reputationVault.fetch(user1VaultPda);
+      await program.methods
+        .refundCredStake()
+        .accounts({
+          market: expiryMarketPda,
+          credStake: user1StakePda,
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+      const user1After = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(user1After.credBalance.toNumber()).to.equal(user1Before.credBalance.toNumber() + 5_000_000);
+
+      const user2Before = await program.account.reputationVault.fetch(user2VaultPda);
+      await program.methods
+        .refundOracleStake()
+        .accounts({
+          market: expiryMarketPda,
+          oracleStake: user2OracleStakePda,
+          reputationVault: user2VaultPda,
+        })
+        .rpc();
+      const user2After = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(user2After.credBalance.toNumber()).to.equal(user2Before.credBalance.toNumber() + 2_000_000);
+
+      const oracleStake = await program.account.oracleStake.fetch(user2OracleStakePda);
+      expect(oracleStake.claimed).to.equal(true);
+
+      await program.methods.auditMarket().accounts({ market: expiryMarketPda }).rpc();
+    });
+  });
+
+  describe("Market Pools", () => {
+    const poolMarketId = "pmkt01";
+    const winnerlessMarketId = "pmkt02";
+    let poolMarketPda;
+    let winnerlessMarketPda;
+    let user1VaultPda;
+    let user2VaultPda;
+    let user1StakePda;
+    let user2StakePda;
+
+    const ipfsHash = new Array(32).fill(11);
+
+    const boost = (market, booster: Keypair, amount: number) =>
+      program.methods
+        .boostMarket(new anchor.BN(amount))
+        .accounts({ market, booster: booster.publicKey })
+        .signers([booster]);
+
+    before(async () => {
+      [poolMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(poolMarketId)],
+        program.programId
+      );
+      [winnerlessMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(winnerlessMarketId)],
+        program.programId
+      );
+      [user1VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2VaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user2.publicKey.toBuffer()],
+        program.programId
+      );
+      [user1StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), poolMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+      );
+      [user2StakePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), poolMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("Funds a market's pool at creation and from boosters", async () => {
+      const creatorBefore = await program.account.reputationVault.fetch(user1VaultPda);
+
+      // The creator seeds the pool in the same transaction that creates the market
+      await program.methods
+        .initializeMarket(tweetUrl, poolMarketId, null)
+        .accounts({
+          market: poolMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .postInstructions([await boost(poolMarketPda, user1, 4_000_000).instruction()])
+        .signers([user1])
+        .rpc();
+      await boost(poolMarketPda, user2, 2_000_000).rpc();
+
+      const market = await program.account.market.fetch(poolMarketPda);
+      expect(market.marketPool.toNumber()).to.equal(6_000_000);
+
+      const creatorAfter = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(creatorAfter.credBalance.toNumber()).to.equal(creatorBefore.credBalance.toNumber() - 4_000_000);
+    });
+
+    it("Pays winners their pool share alongside the pari-mutuel payout", async () => {
+      await program.methods
+        .stakeCred(true, new anchor.BN(2_000_000))
+        .accounts({
+          market: poolMarketPda,
+          reputationVault: user1VaultPda,
+          credStake: user1StakePda,
+          user: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .stakeCred(false, new anchor.BN(2_000_000))
+        .accounts({
+          market: poolMarketPda,
+          reputationVault: user2VaultPda,
+          credStake: user2StakePda,
+          user: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      // No more boosts once the market is resolved
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES
+        .accounts({
+          market: poolMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+      try {
+        await boost(poolMarketPda, user2, 1_000_000).rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotOpen");
+      }
+
+      const vaultBefore = await program.account.reputationVault.fetch(user1VaultPda);
+      await program.methods
+        .claimCredStake()
+        .accounts({
+          market: poolMarketPda,
+          credStake: user1StakePda,
+          reputationVault: user1VaultPda,
+        })
+        .rpc();
+      const vaultAfter = await program.account.reputationVault.fetch(user1VaultPda);
+
+      // 4 Cred pari-mutuel payout plus the whole 6 Cred pool as the only winner,
+      // net of any grant tranche the settlement unlocked
+      const unlocked = vaultBefore.lockedGrant.toNumber() - vaultAfter.lockedGrant.toNumber();
+      expect(vaultAfter.credBalance.toNumber() - vaultBefore.credBalance.toNumber()).to.equal(
+        4_000_000 + 6_000_000 + unlocked
+      );
+
+      const market = await program.account.market.fetch(poolMarketPda);
+      expect(market.marketPoolPaidOut.toNumber()).to.equal(6_000_000);
+
+      await program.methods.auditMarket().accounts({ market: poolMarketPda }).rpc();
+    });
+
+    it("Sweeps an unclaimed pool to the InsightPool after the claim window", async () => {
+      await program.methods
+        .initializeMarket(tweetUrl, winnerlessMarketId, null)
+        .accounts({
+          market: winnerlessMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: user2.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .postInstructions([await boost(winnerlessMarketPda, user2, 3_000_000).instruction()])
+        .signers([user2])
+        .rpc();
+      await program.methods
+        .resolveMarket(0, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // NO, with no stakes
+        .accounts({
+          market: winnerlessMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      const sweep = () => program.methods.sweepMarketPool().accounts({ market: winnerlessMarketPda }).rpc();
+
+      try {
+        await sweep();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("ClaimWindowOpen");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      const poolBefore = await program.account.insightPool.fetch(insightPoolPda);
+      await sweep();
+      const poolAfter = await program.account.insightPool.fetch(insightPoolPda);
+      expect(poolAfter.remainingBudget.toNumber()).to.equal(poolBefore.remainingBudget.toNumber() + 3_000_000);
+      expect(poolAfter.marketPoolsSwept.toNumber()).to.equal(poolBefore.marketPoolsSwept.toNumber() + 3_000_000);
+
+      // The boost is fully accounted for by the sweep
+      const market = await program.account.market.fetch(winnerlessMarketPda);
+      expect(market.marketPoolSweptAmount.toNumber()).to.equal(3_000_000);
+      await program.methods.auditMarket().accounts({ market: winnerlessMarketPda }).rpc();
+
+      try {
+        await sweep();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketPoolSwept");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    it("Refunds boosts to their boosters on a voided market", async () => {
+      const [voidedMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("pmkt03")],
+        program.programId
+      );
+      const boostOf = (user: Keypair) =>
+        PublicKey.findProgramAddressSync(
+          [Buffer.from("market_boost"), voidedMarketPda.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0];
+
+      await program.methods
+        .initializeMarket(tweetUrl, "pmkt03", null)
+        .accounts({
+          market: voidedMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .postInstructions([await boost(voidedMarketPda, user1, 1_000_000).instruction()])
+        .signers([user1])
+        .rpc();
+      await boost(voidedMarketPda, user1, 1_000_000).rpc();
+      await boost(voidedMarketPda, user2, 1_500_000).rpc();
+
+      // Repeat boosts add up on the booster's record
+      const user1Boost = await program.account.marketBoost.fetch(boostOf(user1));
+      expect(user1Boost.amount.toNumber()).to.equal(2_000_000);
+
+      const refund = (user: Keypair, vault: PublicKey) =>
+        program.methods
+          .refundMarketBoost()
+          .accounts({ market: voidedMarketPda, marketBoost: boostOf(user), reputationVault: vault })
+          .rpc();
+
+      // Boosts do not come back while the market is open
+      try {
+        await refund(user1, user1VaultPda);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotResolved");
+      }
+
+      await program.methods
+        .updateProtocolConfig({ marketMaxAge: new anchor.BN(1) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      await program.methods
+        .expireMarket()
+        .accounts({ market: voidedMarketPda, caller: user1.publicKey })
+        .signers([user1])
+        .rpc();
+      await program.methods
+        .updateProtocolConfig({ marketMaxAge: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      const user1Before = await program.account.reputationVault.fetch(user1VaultPda);
+      await refund(user1, user1VaultPda);
+      const user1After = await program.account.reputationVault.fetch(user1VaultPda);
+      expect(user1After.credBalance.toNumber()).to.equal(user1Before.credBalance.toNumber() + 2_000_000);
+
+      try {
+        await refund(user1, user1VaultPda);
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("BoostAlreadyRefunded");
+      }
+
+      const user2Before = await program.account.reputationVault.fetch(user2VaultPda);
+      await refund(user2, user2VaultPda);
+      const user2After = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(user2After.credBalance.toNumber()).to.equal(user2Before.credBalance.toNumber() + 1_500_000);
+
+      let market = await program.account.market.fetch(voidedMarketPda);
+      expect(market.marketPoolRefunded.toNumber()).to.equal(3_500_000);
+
+      // A voided market's pool is never swept away from its boosters
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      try {
+        await program.methods.sweepMarketPool().accounts({ market: voidedMarketPda }).rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketNotResolved");
+      }
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      market = await program.account.market.fetch(voidedMarketPda);
+      expect(market.marketPoolSwept).to.equal(false);
+      await program.methods.auditMarket().accounts({ market: voidedMarketPda }).rpc();
+    });
+
+    it("Refunds boosts to their boosters on a market resolved as Invalid", async () => {
+      const [invalidMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("pmkt04")],
+        program.programId
+      );
+      const [user2BoostPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market_boost"), invalidMarketPda.toBuffer(), user2.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initializeMarket(tweetUrl, "pmkt04", null)
+        .accounts({
+          market: invalidMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      await boost(invalidMarketPda, user2, 1_000_000).rpc();
+      await program.methods
+        .resolveMarket(2, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // INVALID
+        .accounts({
+          market: invalidMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      // Nobody can claim an Invalid market's pool, so it is not swept either
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(0) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+      try {
+        await program.methods.sweepMarketPool().accounts({ market: invalidMarketPda }).rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("MarketInvalid");
+      }
+      await program.methods
+        .updateProtocolConfig({ marketPoolClaimWindow: new anchor.BN(30 * 86_400) })
+        .accounts({ authority: provider.wallet.publicKey })
+        .rpc();
+
+      const user2Before = await program.account.reputationVault.fetch(user2VaultPda);
+      await program.methods
+        .refundMarketBoost()
+        .accounts({ market: invalidMarketPda, marketBoost: user2BoostPda, reputationVault: user2VaultPda })
+        .rpc();
+      const user2After = await program.account.reputationVault.fetch(user2VaultPda);
+      expect(user2After.credBalance.toNumber()).to.equal(user2Before.credBalance.toNumber() + 1_000_000);
+
+      const market = await program.account.market.fetch(invalidMarketPda);
+      expect(market.marketPoolRefunded.toNumber()).to.equal(1_000_000);
+      await program.methods.auditMarket().accounts({ market: invalidMarketPda }).rpc();
+    });
+  });
+
+  // Token mode is global and irreversible, so this runs last
+  describe("Cred Token Mode", () => {
+    const tokenMarketId = "tmkt01";
+    const yesUser = Keypair.generate();
+    const noUser = Keypair.generate();
+    let credMintPda;
+    let tokenMarketPda;
+    let marketEscrowPda;
+    let legacyMarketPda;
+
+    const ipfsHash = new Array(32).fill(12);
+
+    const vaultOf = (user: Keypair) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("reputation_vault"), user.publicKey.toBuffer()],
+        program.programId
+      )[0];
+    const stakeOf = (user: Keypair) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("cred_stake"), tokenMarketPda.toBuffer(), user.publicKey.toBuffer()],
+        program.programId
+      )[0];
+    const credAccountOf = (user: Keypair) =>
+      getAssociatedTokenAddressSync(credMintPda, user.publicKey, false, TOKEN_2022_PROGRAM_ID);
+    const tokenBalance = async (account) =>
+      Number((await getAccount(provider.connection, account, "confirmed", TOKEN_2022_PROGRAM_ID)).amount);
+
+    before(async () => {
+      [credMintPda] = PublicKey.findProgramAddressSync([Buffer.from("cred_mint")], program.programId);
+      [tokenMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from(tokenMarketId)],
+        program.programId
+      );
+      [marketEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market_escrow"), tokenMarketPda.toBuffer()],
+        program.programId
+      );
+
+      for (const user of [yesUser, noUser]) {
+        await provider.connection.confirmTransaction(
+          await provider.connection.requestAirdrop(user.publicKey, 10 * LAMPORTS_PER_SOL)
+        );
+      }
+
+      // A stake placed before token mode, still held by its open market
+      [legacyMarketPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), Buffer.from("tmkt00")],
+        program.programId
+      );
+      await program.methods
+        .initializeMarket(tweetUrl, "tmkt00", null)
+        .accounts({
+          market: legacyMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: marketCreator.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([marketCreator])
+        .rpc();
+      await program.methods
+        .stakeCred(true, new anchor.BN(1_000_000))
+        .accounts({
+          market: legacyMarketPda,
+          reputationVault: vaultOf(user1),
+          credStake: PublicKey.findProgramAddressSync(
+            [Buffer.from("cred_stake"), legacyMarketPda.toBuffer(), user1.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          user: user1.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+    });
+
+    it("Switches Cred to a non-transferable Token-2022 mint", async () => {
+      await program.methods
+        .initializeCredMint(true)
+        .accounts({
+          credMint: credMintPda,
+          protocolConfig: protocolConfigPda,
+          authority: provider.wallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+      expect(config.credMint.toBase58()).to.equal(credMintPda.toBase58());
+      expect(config.credNonTransferable).to.equal(true);
+
+      const mint = await getMint(provider.connection, credMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+      expect(getExtensionTypes(mint.tlvData)).to.include(ExtensionType.NonTransferable);
+    });
+
+    it("Mints the initial grant into each new user's Cred token account", async () => {
+      const config = await program.account.protocolConfig.fetch(protocolConfigPda);
+
+      for (const user of [yesUser, noUser]) {
+        await createAssociatedTokenAccount(
+          provider.connection,
+          provider.wallet.payer,
+          credMintPda,
+          user.publicKey,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        );
+        await program.methods
+          .initializeReputationVault()
+          .accounts({
+            reputationVault: vaultOf(user),
+            owner: user.publicKey,
+            payer: provider.wallet.publicKey,
+            credMint: credMintPda,
+            credAccount: credAccountOf(user),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+
+        const vault = await program.account.reputationVault.fetch(vaultOf(user));
+        expect(vault.credBalance.toNumber()).to.equal(config.unattestedGrant.toNumber());
+        expect(await tokenBalance(credAccountOf(user))).to.equal(config.unattestedGrant.toNumber());
+      }
+    });
+
+    it("Escrows stakes and pays the winner out of the market escrow", async () => {
+      await program.methods
+        .initializeMarket(tweetUrl, tokenMarketId, null)
+        .accounts({
+          market: tokenMarketPda,
+          agentExecutor: agentExecutorPda,
+          creator: yesUser.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([yesUser])
+        .rpc();
+      await program.methods
+        .initializeMarketEscrow()
+        .accounts({
+          market: tokenMarketPda,
+          credMint: credMintPda,
+          marketEscrow: marketEscrowPda,
+          payer: provider.wallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const stake = (user: Keypair, direction: boolean, amount: number) =>
+        program.methods
+          .stakeCred(direction, new anchor.BN(amount))
+          .accounts({
+            market: tokenMarketPda,
+            reputationVault: vaultOf(user),
+            credStake: stakeOf(user),
+            user: user.publicKey,
+            systemProgram: SystemProgram.programId,
+            credMint: credMintPda,
+            credAccount: credAccountOf(user),
+            marketEscrow: marketEscrowPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([user])
+          .rpc();
+
+      const yesBefore = await tokenBalance(credAccountOf(yesUser));
+      const noBefore = await tokenBalance(credAccountOf(noUser));
+      await stake(yesUser, true, 5_000_000);
+      await stake(noUser, false, 3_000_000);
+      expect(await tokenBalance(marketEscrowPda)).to.equal(8_000_000);
+      expect(await tokenBalance(credAccountOf(yesUser))).to.equal(yesBefore - 5_000_000);
+      expect(await tokenBalance(credAccountOf(noUser))).to.equal(noBefore - 3_000_000);
+
+      await program.methods
+        .resolveMarket(1, ipfsHash, 9000, "test-model", ipfsHash, ipfsHash, null) // YES
+        .accounts({
+          market: tokenMarketPda,
+          agentExecutor: agentExecutorPda,
+          authority: agentExecutorAuthority.publicKey,
+        })
+        .signers([agentExecutorAuthority])
+        .rpc();
+
+      const claim = async (user: Keypair) => {
+        const before = await program.account.reputationVault.fetch(vaultOf(user));
+        await program.methods
+          .claimCredStake()
+          .accounts({
+            market: tokenMarketPda,
+            credStake: stakeOf(user),
+            reputationVault: vaultOf(user),
+            credMint: credMintPda,
+            credAccount: credAccountOf(user),
+            marketEscrow: marketEscrowPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        const after = await program.account.reputationVault.fetch(vaultOf(user));
+        return before.lockedGrant.toNumber() - after.lockedGrant.toNumber();
+      };
+
+      // The winner takes the whole 8 Cred escrow; both sides unlock a grant tranche
+      const yesUnlocked = await claim(yesUser);
+      const noUnlocked = await claim(noUser);
+      expect(await tokenBalance(marketEscrowPda)).to.equal(0);
+      expect(await tokenBalance(credAccountOf(yesUser))).to.equal(yesBefore + 3_000_000 + yesUnlocked);
+      expect(await tokenBalance(credAccountOf(noUser))).to.equal(noBefore - 3_000_000 + noUnlocked);
+
+      // Vault balances stay in lockstep with the token accounts
+      for (const user of [yesUser, noUser]) {
+        const vault = await program.account.reputationVault.fetch(vaultOf(user));
+        expect(vault.credBalance.toNumber()).to.equal(await tokenBalance(credAccountOf(user)));
+      }
+
+      const market = await program.account.market.fetch(tokenMarketPda);
+      expect(market.totalPaidOut.toNumber()).to.equal(8_000_000);
+      await program.methods
+        .auditMarket()
+        .accounts({
+          market: tokenMarketPda,
+          credMint: credMintPda,
+          marketEscrow: marketEscrowPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    });
+
+    it("Backs Cred that existed before token mode with tokens", async () => {
+      await createAssociatedTokenAccount(
+        provider.connection,
+        provider.wallet.payer,
+        credMintPda,
+        user1.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      // An unmigrated vault's balance has no tokens behind it, so it cannot move any
+      try {
+        await program.methods
+          .boostMarket(new anchor.BN(1_000_000))
+          .accounts({
+            market: legacyMarketPda,
+            booster: user1.publicKey,
+            credMint: credMintPda,
+            credAccount: credAccountOf(user1),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("VaultNotTokenBacked");
+      }
+
+      const migrate = () =>
+        program.methods
+          .migrateVaultToToken()
+          .accounts({
+            reputationVault: vaultOf(user1),
+            credMint: credMintPda,
+            credAccount: credAccountOf(user1),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+      await migrate();
+      const vault = await program.account.reputationVault.fetch(vaultOf(user1));
+      expect(vault.tokenBacked).to.equal(true);
+      expect(await tokenBalance(credAccountOf(user1))).to.equal(vault.credBalance.toNumber());
+
+      try {
+        await migrate();
+        expect.fail("Should have thrown");
+      } catch (error) {
+        expect(error.message).to.include("VaultAlreadyTokenBacked");
+      }
+
+      // The market's escrow is opened holding the stake placed before token mode
+      const [legacyEscrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market_escrow"), legacyMarketPda.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .initializeMarketEscrow()
+        .accounts({
+          market: legacyMarketPda,
+          credMint: credMintPda,
+          marketEscrow: legacyEscrowPda,
+          payer: provider.wallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+      expect(await tokenBalance(legacyEscrowPda)).to.equal(1_000_000);
+      await program.methods
+        .auditMarket()
+        .accounts({
+          market: legacyMarketPda,
+          credMint: credMintPda,
+          marketEscrow: legacyEscrowPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
     });
   });
 });
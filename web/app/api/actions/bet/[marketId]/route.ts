@@ -11,6 +11,8 @@ const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || process.env.RPC_URL || 'https
 const MARKET_SEED = Buffer.from('market');
 const REPUTATION_VAULT_SEED = Buffer.from('reputation_vault');
 const CRED_STAKE_SEED = Buffer.from('cred_stake');
+const PROTOCOL_CONFIG_SEED = Buffer.from('protocol_config');
+const CRED_LEDGER_SEED = Buffer.from('cred_ledger');
 
 // CORS headers for Blinks
 const corsHeaders = {
@@ -37,6 +39,14 @@ function findReputationVaultPda(owner: PublicKey): [PublicKey, number] {
     );
 }
 
+function findProtocolConfigPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([PROTOCOL_CONFIG_SEED], PROPHECY_PROGRAM_ID);
+}
+
+function findCredLedgerPda(): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([CRED_LEDGER_SEED], PROPHECY_PROGRAM_ID);
+}
+
 function findCredStakePda(marketPda: PublicKey, user: PublicKey): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
         [CRED_STAKE_SEED, marketPda.toBuffer(), user.toBuffer()],
@@ -175,6 +185,8 @@ export async function POST(
         const [marketPda] = findMarketPda(marketId);
         const [reputationVaultPda] = findReputationVaultPda(userPubkey);
         const [credStakePda] = findCredStakePda(marketPda, userPubkey);
+        const [protocolConfigPda] = findProtocolConfigPda();
+        const [credLedgerPda] = findCredLedgerPda();
 
         // NOTE: We skip on-chain validation here to prevent RPC timeout.
         // If market or vault doesn't exist, the transaction will fail at signing time
@@ -199,6 +211,14 @@ export async function POST(
                 { pubkey: credStakePda, isSigner: false, isWritable: true },
                 { pubkey: userPubkey, isSigner: true, isWritable: true },
                 { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+                { pubkey: protocolConfigPda, isSigner: false, isWritable: false },
+                { pubkey: credLedgerPda, isSigner: false, isWritable: true },
+                // Cred token-mode accounts (mint, token account, escrow, token program) are
+                // omitted; the program ID marks an absent optional account
+                { pubkey: PROPHECY_PROGRAM_ID, isSigner: false, isWritable: false },
+                { pubkey: PROPHECY_PROGRAM_ID, isSigner: false, isWritable: false },
+                { pubkey: PROPHECY_PROGRAM_ID, isSigner: false, isWritable: false },
+                { pubkey: PROPHECY_PROGRAM_ID, isSigner: false, isWritable: false },
             ],
             data: instructionData,
         });
@@ -22,7 +22,7 @@ interface MarketData {
     id: string;
     question: string;
     tweetUrl: string;
-    status: 'open' | 'resolved' | 'disputed';
+    status: 'open' | 'resolved' | 'disputed' | 'voided';
     outcome?: 'YES' | 'NO';
     totalYesStake: number;
     totalNoStake: number;
@@ -90,10 +90,11 @@ export default function MarketPage({ params }: MarketPageProps) {
                         // eslint-disable-next-line @typescript-eslint/no-explicit-any
                         const marketAccount = await (program.account as any).market.fetch(marketPda);
 
-                        const statusMap: Record<string, 'open' | 'resolved' | 'disputed'> = {
+                        const statusMap: Record<string, 'open' | 'resolved' | 'disputed' | 'voided'> = {
                             'open': 'open',
                             'resolved': 'resolved',
-                            'disputed': 'disputed'
+                            'disputed': 'disputed',
+                            'voided': 'voided'
                         };
 
                         const statusKey = Object.keys(marketAccount.status)[0];
@@ -166,7 +167,7 @@ export default function MarketPage({ params }: MarketPageProps) {
         fetchVault();
     }, [program, publicKey]);
 
-    // Initialize user's ReputationVault (grants 100 Cred)
+    // Initialize user's ReputationVault (grants starter Cred, the rest unlocks as stakes settle)
     const handleInitializeVault = useCallback(async () => {
         if (!connected || !publicKey || !program) {
             alert('Please connect your wallet first');
@@ -192,8 +193,12 @@ export default function MarketPage({ params }: MarketPageProps) {
                 .rpc();
 
             console.log('Vault initialized:', tx);
-            alert('🎉 Success! You now have 100 Cred to stake!');
-            setUserVaultBalance(100);
+            alert('🎉 Success! Your starter Cred is ready to stake - the rest of your grant unlocks as your stakes settle.');
+
+            // Only part of the grant is unlocked up front, so read the balance back
+            // eslint-disable-next-line @typescript-eslint/no-explicit-any
+            const vault = await (program.account as any).reputationVault.fetch(vaultPda);
+            setUserVaultBalance(vault.credBalance.toNumber() / 1_000_000);
             setNeedsVault(false);
         } catch (err: unknown) {
             const errorMessage = err instanceof Error ? err.message : 'Unknown error';
@@ -396,24 +401,26 @@ export default function MarketPage({ params }: MarketPageProps) {
                                 ) : userVaultBalance < 50 ? (
                                     <button
                                         onClick={async () => {
+                                            if (!program || !publicKey) return;
                                             try {
-                                                const res = await fetch('http://localhost:3001/faucet', {
-                                                    method: 'POST',
-                                                    headers: { 'Content-Type': 'application/json' },
-                                                    body: JSON.stringify({ walletAddress: publicKey?.toBase58() }),
-                                                });
-                                                const data = await res.json();
-                                                alert(data.message);
-                                                if (data.success) {
-                                                    setUserVaultBalance(prev => prev + 100);
-                                                }
-                                            } catch {
-                                                alert('Faucet unavailable. Try again later.');
+                                                // The faucet is self-serve: the user signs claim_faucet directly
+                                                // eslint-disable-next-line @typescript-eslint/no-explicit-any
+                                                await (program.methods as any)
+                                                    .claimFaucet()
+                                                    .accounts({ user: publicKey })
+                                                    .rpc();
+                                                alert('💰 25 Cred added to your vault!');
+                                                setUserVaultBalance(prev => prev + 25);
+                                            } catch (err: unknown) {
+                                                const message = err instanceof Error ? err.message : String(err);
+                                                alert(message.includes('FaucetCooldown')
+                                                    ? 'Faucet cooldown active - try again tomorrow.'
+                                                    : 'Faucet unavailable. Try again later.');
                                             }
                                         }}
                                         className="text-sm bg-gradient-to-r from-yellow-500 to-orange-500 text-white px-3 py-1 rounded-lg hover:scale-105 transition-transform"
                                     >
-                                        💰 Get 100 Cred
+                                        💰 Get 25 Cred
                                     </button>
                                 ) : (
                                     <span className="text-sm text-cyan-400">
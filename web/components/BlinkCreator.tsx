@@ -132,7 +132,7 @@ export default function BlinkCreator({ onMarketCreated }: BlinkCreatorProps) {
 
                     // Call initialize_market instruction
                     signature = await program.methods
-                        .initializeMarket(tweetUrl, marketId)
+                        .initializeMarket(tweetUrl, marketId, null)
                         .accounts({
                             market: marketPda,
                             agentExecutor: agentExecutorPda,
@@ -7,6 +7,8 @@ import { PublicKey } from '@solana/web3.js';
 
 const PROPHECY_PROGRAM_ID = new PublicKey('UJW3ZdLcVxYuYDRpy6suu2DHCQhkUgCGKPUaDqdzSs4');
 const REPUTATION_VAULT_SEED = Buffer.from('reputation_vault');
+const LEADERBOARD_SEED = Buffer.from('leaderboard');
+const LEADERBOARD_METRIC_ACCURACY = 1; // LeaderboardMetric::Accuracy
 
 interface LeaderboardEntry {
     rank: number;
@@ -37,15 +39,68 @@ export default function Leaderboard({ entries: propEntries, userAddress }: Leade
             return;
         }
 
+        // Read the on-chain top-N Accuracy leaderboard in one fetch, plus the listed vaults
+        const fetchOnChainLeaderboard = async (): Promise<LeaderboardEntry[] | null> => {
+            const [leaderboardPda] = PublicKey.findProgramAddressSync(
+                [LEADERBOARD_SEED, Buffer.from([LEADERBOARD_METRIC_ACCURACY])],
+                PROPHECY_PROGRAM_ID
+            );
+            const info = await connection.getAccountInfo(leaderboardPda);
+            if (!info) return null;
+
+            // Leaderboard layout: discriminator (8) + metric (1) + entries len (4) + entries (owner 32 + score 8)
+            const data = info.data;
+            const count = data.readUInt32LE(9);
+            const ranked = Array.from({ length: count }, (_, i) => {
+                const offset = 13 + i * 40;
+                return {
+                    owner: new PublicKey(data.slice(offset, offset + 32)),
+                    score: Number(data.readBigUInt64LE(offset + 32)),
+                };
+            });
+            if (ranked.length === 0) return null;
+
+            const vaultPdas = ranked.map(({ owner }) =>
+                PublicKey.findProgramAddressSync([REPUTATION_VAULT_SEED, owner.toBuffer()], PROPHECY_PROGRAM_ID)[0]
+            );
+            const vaults = await connection.getMultipleAccountsInfo(vaultPdas);
+
+            return ranked.map(({ owner, score }, index) => {
+                const vaultData = vaults[index]?.data;
+                const address = owner.toBase58();
+                return {
+                    rank: index + 1,
+                    address: address.substring(0, 4) + '...' + address.slice(-4),
+                    credBalance: vaultData ? Number(vaultData.readBigUInt64LE(40)) / 1_000_000 : 0,
+                    accuracy: Math.round(score / 100), // Basis points to percent
+                    predictions: vaultData ? Number(vaultData.readBigUInt64LE(64)) : 0,
+                    isYou: publicKey ? address === publicKey.toBase58() : false,
+                };
+            });
+        };
+
         const fetchReputationVaults = async () => {
             try {
+                const onChainEntries = await fetchOnChainLeaderboard();
+                if (onChainEntries) {
+                    setEntries(onChainEntries);
+                    setLoading(false);
+                    return;
+                }
+
+                // Fall back to scanning every vault when no on-chain leaderboard exists yet
                 // Query all ReputationVault accounts from the program
                 // ReputationVault layout: owner (32) + cred_balance (8) + total_earned (8) +
-                //                        total_staked (8) + participation_count (8) + bump (1)
-                // Total: 8 (discriminator) + 32 + 8 + 8 + 8 + 8 + 1 = 73 bytes
+                //                        total_staked (8) + participation_count (8) + wins (8) +
+                //                        losses (8) + settled_count (8) + brier_score_total (8) +
+                //                        transfer_day (8) + transferred_today (8) + referrer (33) +
+                //                        referral_count (4) + referral_bonus_paid (1) + locked_grant (8) +
+                //                        last_faucet_claim (8) + earn_day (8) +
+                //                        earned_today_by_method (8 * 7) + earned_by_method (8 * 7) + bump (1)
+                // Total: 8 (discriminator) + 32 + 8 * 13 + 33 + 4 + 1 + 8 * 14 + 1 = 295 bytes
                 const accounts = await connection.getProgramAccounts(PROPHECY_PROGRAM_ID, {
                     filters: [
-                        { dataSize: 73 }, // ReputationVault size
+                        { dataSize: 295 }, // ReputationVault size
                     ],
                 });
 
@@ -61,13 +116,13 @@ export default function Leaderboard({ entries: propEntries, userAddress }: Leade
                     // Skip 8-byte discriminator
                     const owner = new PublicKey(data.slice(8, 40));
                     const credBalance = Number(data.readBigUInt64LE(40)) / 1_000_000; // Convert from micro-Cred
-                    const totalEarned = Number(data.readBigUInt64LE(48)) / 1_000_000;
-                    const totalStaked = Number(data.readBigUInt64LE(56)) / 1_000_000;
                     const participationCount = Number(data.readBigUInt64LE(64));
+                    const wins = Number(data.readBigUInt64LE(72));
+                    const losses = Number(data.readBigUInt64LE(80));
 
-                    // Calculate accuracy as ratio of earned vs staked (simplified metric)
-                    const accuracy = totalStaked > 0
-                        ? Math.min(100, Math.round((totalEarned / totalStaked) * 50))
+                    // Accuracy is the share of decided stakes that were won
+                    const accuracy = wins + losses > 0
+                        ? Math.round((wins / (wins + losses)) * 100)
                         : 0;
 
                     return {